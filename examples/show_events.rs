@@ -0,0 +1,14 @@
+//! Print nl80211 notifications as they are received.
+
+use nl80211::{MulticastGroup, Socket};
+
+fn main() -> Result<(), nl80211::Error> {
+    let mut socket = Socket::connect()?;
+    socket.subscribe(&MulticastGroup::ALL)?;
+
+    for event in socket.events() {
+        println!("{:?}", event?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! Pretty print the capabilities of the wireless hardware devices.
+
+use nl80211::Socket;
+
+fn main() -> Result<(), nl80211::Error> {
+    let wiphys = Socket::connect()?.get_wiphys()?;
+
+    for wiphy in wiphys {
+        println!("{}\n", wiphy);
+    }
+
+    Ok(())
+}
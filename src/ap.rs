@@ -0,0 +1,439 @@
+use crate::attr::{Nl80211Attr, Nl80211AuthType, Nl80211HiddenSsid, Nl80211WpaVersions};
+use crate::channel::ChannelDef;
+use crate::connect::crypto_attrs;
+use crate::error::Error;
+use crate::ie::{parse_information_elements, AkmSuite, CipherSuite, InformationElement, Rsn};
+use crate::nl80211traits::ToNlAttributes;
+use macaddr::MacAddr;
+use neli::nlattr::Nlattr;
+use std::convert::TryFrom;
+
+/// Capability bit of an infrastructure BSS
+pub const CAPABILITY_ESS: u16 = 1 << 0;
+/// Capability bit of a BSS requiring encryption
+pub const CAPABILITY_PRIVACY: u16 = 1 << 4;
+/// Capability bit of a BSS allowing short preambles
+pub const CAPABILITY_SHORT_PREAMBLE: u16 = 1 << 5;
+/// Capability bit of a BSS using short slot times
+pub const CAPABILITY_SHORT_SLOT_TIME: u16 = 1 << 10;
+
+/// Maximum number of rates of the Supported Rates element, other rates are advertised in the
+/// Extended Supported Rates element
+const MAX_SUPPORTED_RATES: usize = 8;
+/// Maximum length of a SSID
+const MAX_SSID_LEN: usize = 32;
+
+/// A beacon frame, split by nl80211 into a head and a tail around the TIM element
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beacon {
+    /// BSSID of the access point (MAC address of the interface)
+    pub bssid: MacAddr,
+    /// SSID of the network
+    pub ssid: String,
+    /// How the SSID is hidden from beacons
+    pub hidden_ssid: Nl80211HiddenSsid,
+    /// Beacon interval in time units (1024 µs)
+    pub interval: u16,
+    /// Capability information (see the `CAPABILITY_*` constants)
+    pub capability: u16,
+    /// Rates in units of 500 kb/s, the highest bit marks basic rates
+    pub rates: Vec<u8>,
+    /// Channel number advertised in the DS Parameter Set element
+    pub channel: Option<u8>,
+    /// Security parameters advertised in the RSN element, the privacy capability bit is set
+    /// along. The access point configuration fills it from its cipher suites when not set
+    pub rsn: Option<Rsn>,
+    /// Information elements added after the TIM element (HT capabilities, WPA...)
+    pub ies: Vec<u8>,
+}
+
+impl Beacon {
+    /// Create the beacon of an open network with the 802.11g rates and a 100 TU interval
+    pub fn new(bssid: MacAddr, ssid: &str) -> Beacon {
+        Beacon {
+            bssid,
+            ssid: ssid.into(),
+            hidden_ssid: Nl80211HiddenSsid::HiddenSsidNotInUse,
+            interval: 100,
+            capability: CAPABILITY_ESS | CAPABILITY_SHORT_SLOT_TIME,
+            rates: vec![
+                0x82, 0x84, 0x8B, 0x96, 0x0C, 0x12, 0x18, 0x24, 0x30, 0x48, 0x60, 0x6C,
+            ],
+            channel: None,
+            rsn: None,
+            ies: Vec::new(),
+        }
+    }
+
+    /// Management header, fixed fields and the elements preceding the TIM element
+    ///
+    /// Fails when the SSID is longer than 32 bytes.
+    pub fn head(&self) -> Result<Vec<u8>, Error> {
+        if self.ssid.len() > MAX_SSID_LEN {
+            return Err(Error::Invalid(format!(
+                "SSID of {} bytes exceeds the maximum of {} bytes",
+                self.ssid.len(),
+                MAX_SSID_LEN
+            )));
+        }
+
+        // Frame control (beacon) and duration
+        let mut head = vec![0x80, 0x00, 0x00, 0x00];
+        head.extend(&[0xFF; 6]);
+        head.extend(self.bssid.as_bytes());
+        head.extend(self.bssid.as_bytes());
+        // Sequence control and timestamp, both set by the driver
+        head.extend(&[0; 10]);
+        head.extend(&self.interval.to_le_bytes());
+        let capability = match self.rsn {
+            Some(_) => self.capability | CAPABILITY_PRIVACY,
+            None => self.capability,
+        };
+        head.extend(&capability.to_le_bytes());
+
+        let ssid = match self.hidden_ssid {
+            Nl80211HiddenSsid::HiddenSsidZeroLen => Vec::new(),
+            Nl80211HiddenSsid::HiddenSsidZeroContents => vec![0; self.ssid.len()],
+            _ => self.ssid.as_bytes().to_vec(),
+        };
+        push_element(&mut head, 0, &ssid)?;
+
+        let rates = &self.rates[..self.rates.len().min(MAX_SUPPORTED_RATES)];
+        push_element(&mut head, 1, rates)?;
+
+        if let Some(channel) = self.channel {
+            push_element(&mut head, 3, &[channel])?;
+        }
+
+        Ok(head)
+    }
+
+    /// Elements following the TIM element
+    pub fn tail(&self) -> Result<Vec<u8>, Error> {
+        let mut tail = Vec::new();
+
+        if self.rates.len() > MAX_SUPPORTED_RATES {
+            push_element(&mut tail, 50, &self.rates[MAX_SUPPORTED_RATES..])?;
+        }
+
+        if let Some(rsn) = &self.rsn {
+            push_element(&mut tail, 48, &rsn.to_bytes())?;
+        }
+
+        tail.extend(&self.ies);
+        Ok(tail)
+    }
+}
+
+/// Append an information element to a frame, failing when its content exceeds 255 bytes
+fn push_element(frame: &mut Vec<u8>, id: u8, data: &[u8]) -> Result<(), Error> {
+    let len = u8::try_from(data.len()).map_err(|_| {
+        Error::Invalid(format!(
+            "Information element {} of {} bytes exceeds the maximum of 255 bytes",
+            id,
+            data.len()
+        ))
+    })?;
+
+    frame.push(id);
+    frame.push(len);
+    frame.extend(data);
+    Ok(())
+}
+
+impl ToNlAttributes for Beacon {
+    /// Build the attributes of the nl80211 command CmdSetBeacon
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        Ok(vec![
+            Nlattr::new(None, Nl80211Attr::AttrBeaconHead, self.head()?)?,
+            Nlattr::new(None, Nl80211Attr::AttrBeaconTail, self.tail()?)?,
+        ])
+    }
+}
+
+/// Configuration of an access point started with the nl80211 command CmdStartAp
+///
+/// The SSID, its visibility and the beacon interval are taken from the beacon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApConfig {
+    /// Beacon of the access point
+    pub beacon: Beacon,
+    /// DTIM period, in beacon intervals
+    pub dtim_period: u32,
+    /// Channel of the access point, the current channel of the wiphy is used when not set
+    pub channel: Option<ChannelDef>,
+    /// Authentication algorithm accepted by the access point
+    pub auth_type: Option<Nl80211AuthType>,
+    /// WPA versions in use
+    pub wpa_versions: Vec<Nl80211WpaVersions>,
+    /// Pairwise cipher suites, the network is protected when a cipher suite is set. The beacon of
+    /// a protected network must advertise it, see [`protected_beacon`](#method.protected_beacon)
+    pub pairwise_ciphers: Vec<CipherSuite>,
+    /// Group cipher suite
+    pub group_cipher: Option<CipherSuite>,
+    /// Authentication and key management suites
+    pub akm_suites: Vec<AkmSuite>,
+    /// PMK for the drivers handling the 4-way handshake of WPA2-PSK networks
+    pub pmk: Option<Vec<u8>>,
+    /// Extra information elements added to probe responses
+    pub probe_resp_ies: Option<Vec<u8>>,
+    /// Extra information elements added to (re)association responses
+    pub assoc_resp_ies: Option<Vec<u8>>,
+}
+
+impl ApConfig {
+    /// Create the configuration of an open access point with a DTIM period of 2
+    pub fn new(beacon: Beacon) -> ApConfig {
+        ApConfig {
+            beacon,
+            dtim_period: 2,
+            channel: None,
+            auth_type: None,
+            wpa_versions: Vec::new(),
+            pairwise_ciphers: Vec::new(),
+            group_cipher: None,
+            akm_suites: Vec::new(),
+            pmk: None,
+            probe_resp_ies: None,
+            assoc_resp_ies: None,
+        }
+    }
+
+    /// Beacon sent to the kernel, consistent with the cipher suites of the configuration
+    ///
+    /// The beacon of a protected network gets the privacy capability bit. Unless set, its RSN
+    /// element is built from the cipher and AKM suites when WPA2 is in use. WPA networks must
+    /// add the WPA element to the information elements of the beacon.
+    pub fn protected_beacon(&self) -> Result<Beacon, Error> {
+        let mut beacon = self.beacon.clone();
+        if self.pairwise_ciphers.is_empty() && self.group_cipher.is_none() {
+            return Ok(beacon);
+        }
+
+        beacon.capability |= CAPABILITY_PRIVACY;
+
+        let wpa2 = self.wpa_versions.is_empty()
+            || self.wpa_versions.contains(&Nl80211WpaVersions::WpaVersion2);
+        if wpa2 && beacon.rsn.is_none() {
+            beacon.rsn = Some(Rsn {
+                version: 1,
+                group_cipher: self
+                    .group_cipher
+                    .or_else(|| self.pairwise_ciphers.first().copied()),
+                pairwise_ciphers: self.pairwise_ciphers.clone(),
+                akm_suites: self.akm_suites.clone(),
+                capabilities: Some(0),
+            });
+        }
+
+        let wpa = parse_information_elements(&beacon.ies)
+            .iter()
+            .any(|element| matches!(element, InformationElement::Wpa(_)));
+        if beacon.rsn.is_none() && !wpa {
+            return Err(Error::Invalid(
+                "The beacon of a protected network must advertise a RSN or WPA element".into(),
+            ));
+        }
+
+        Ok(beacon)
+    }
+}
+
+impl ToNlAttributes for ApConfig {
+    /// Build the attributes of the nl80211 command CmdStartAp
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = self.protected_beacon()?.to_attrs()?;
+
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrBeaconInterval,
+            u32::from(self.beacon.interval).to_le_bytes().to_vec(),
+        )?);
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrDtimPeriod,
+            self.dtim_period.to_le_bytes().to_vec(),
+        )?);
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrSsid,
+            self.beacon.ssid.as_bytes().to_vec(),
+        )?);
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrHiddenSsid,
+            u32::from(u16::from(&self.beacon.hidden_ssid))
+                .to_le_bytes()
+                .to_vec(),
+        )?);
+
+        if let Some(channel) = &self.channel {
+            attrs.extend(channel.to_attrs()?);
+        }
+
+        if let Some(auth_type) = &self.auth_type {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        attrs.extend(crypto_attrs(
+            true,
+            &self.wpa_versions,
+            &self.pairwise_ciphers,
+            self.group_cipher,
+            &self.akm_suites,
+        )?);
+
+        if let Some(pmk) = &self.pmk {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrPmk, pmk.clone())?);
+        }
+
+        if let Some(ies) = &self.probe_resp_ies {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrIeProbeResp,
+                ies.clone(),
+            )?);
+        }
+
+        if let Some(ies) = &self.assoc_resp_ies {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrIeAssocResp,
+                ies.clone(),
+            )?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test_ap {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use crate::attr::Nl80211ChanWidth;
+
+    #[test]
+    fn test_beacon_frame() {
+        let beacon = Beacon {
+            channel: Some(1),
+            ..Beacon::new(MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]), "test")
+        };
+
+        let head = beacon.head().unwrap();
+
+        assert_eq!(
+            &head[..36],
+            &[
+                0x80, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, 0x00, 0x00, 0x00,
+                0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0,
+                100, 0, 0x01, 0x04,
+            ][..]
+        );
+        assert_eq!(
+            &head[36..],
+            &[
+                0, 4, 116, 101, 115, 116, 1, 8, 0x82, 0x84, 0x8B, 0x96, 0x0C, 0x12, 0x18, 0x24, 3,
+                1, 1,
+            ][..]
+        );
+        assert_eq!(beacon.tail().unwrap(), vec![50, 4, 0x30, 0x48, 0x60, 0x6C]);
+    }
+
+    #[test]
+    fn test_invalid_beacon() {
+        let bssid = MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]);
+
+        let beacon = Beacon::new(bssid, &"a".repeat(33));
+        assert!(matches!(beacon.to_attrs(), Err(Error::Invalid(_))));
+
+        let beacon = Beacon {
+            rates: vec![0x0C; 300],
+            ..Beacon::new(bssid, "test")
+        };
+        assert!(matches!(beacon.tail(), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn test_hidden_ssid() {
+        let beacon = Beacon {
+            hidden_ssid: Nl80211HiddenSsid::HiddenSsidZeroContents,
+            ..Beacon::new(MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]), "test")
+        };
+
+        assert_eq!(&beacon.head().unwrap()[36..42], &[0, 4, 0, 0, 0, 0][..]);
+    }
+
+    #[test]
+    fn test_psk_beacon() {
+        let config = ApConfig {
+            wpa_versions: vec![Nl80211WpaVersions::WpaVersion2],
+            pairwise_ciphers: vec![CipherSuite::Ccmp],
+            group_cipher: Some(CipherSuite::Ccmp),
+            akm_suites: vec![AkmSuite::Psk],
+            ..ApConfig::new(Beacon::new(
+                MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]),
+                "test",
+            ))
+        };
+
+        let attrs = config.to_attrs().unwrap();
+
+        assert_eq!(attrs[0].nla_type, AttrBeaconHead);
+        assert_eq!(&attrs[0].payload[34..36], &[0x11, 0x04][..]);
+        assert_eq!(
+            attrs[1].payload,
+            vec![
+                50, 4, 0x30, 0x48, 0x60, 0x6C, 48, 20, 1, 0, 0x00, 0x0F, 0xAC, 4, 1, 0, 0x00, 0x0F,
+                0xAC, 4, 1, 0, 0x00, 0x0F, 0xAC, 2, 0, 0,
+            ]
+        );
+
+        // WPA networks must provide their WPA element
+        let wpa = ApConfig {
+            wpa_versions: vec![Nl80211WpaVersions::WpaVersion1],
+            ..config
+        };
+        assert!(matches!(wpa.to_attrs(), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn test_ap_config_to_attrs() {
+        let config = ApConfig {
+            channel: Some(ChannelDef {
+                frequency: 2412,
+                width: Some(Nl80211ChanWidth::ChanWidth20Noht),
+                center_freq1: Some(2412),
+                center_freq2: None,
+                channel_type: None,
+            }),
+            ..ApConfig::new(Beacon::new(
+                MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]),
+                "test",
+            ))
+        };
+
+        let attrs = config.to_attrs().unwrap();
+        let types: Vec<Nl80211Attr> = attrs.iter().map(|attr| attr.nla_type.clone()).collect();
+
+        assert_eq!(
+            types,
+            vec![
+                AttrBeaconHead,
+                AttrBeaconTail,
+                AttrBeaconInterval,
+                AttrDtimPeriod,
+                AttrSsid,
+                AttrHiddenSsid,
+                AttrWiphyFreq,
+                AttrChannelWidth,
+                AttrCenterFreq1,
+            ]
+        );
+        assert_eq!(attrs[3].payload, vec![2, 0, 0, 0]);
+    }
+}
@@ -254,9 +254,66 @@ impl_var_trait!(
     AttrNanDual                      => 239,
     AttrNanFunc                      => 240,
     AttrNanMatch                     => 241,
-    AttrAfterLast                    => 242,
-    NumAttr                          => 242,//__AttrAfterLast,
-    AttrMax                          => 241//__AttrAfterLast - 1
+    AttrFilsKek                      => 242,
+    AttrFilsNonces                   => 243,
+    AttrMulticastToUnicastEnabled    => 244,
+    AttrBssid                        => 245,
+    AttrSchedScanRelativeRssi        => 246,
+    AttrSchedScanRssiAdjust          => 247,
+    AttrTimeoutReason                => 248,
+    AttrFilsErpUsername              => 249,
+    AttrFilsErpRealm                 => 250,
+    AttrFilsErpNextSeqNum            => 251,
+    AttrFilsErpRrk                   => 252,
+    AttrFilsCacheId                  => 253,
+    AttrPmk                          => 254,
+    AttrSchedScanMulti               => 255,
+    AttrSchedScanMaxReqs             => 256,
+    AttrWant1x4wayHs                 => 257,
+    AttrPmkr0Name                    => 258,
+    AttrPortAuthorized               => 259,
+    AttrExternalAuthAction           => 260,
+    AttrExternalAuthSupport          => 261,
+    AttrNss                          => 262,
+    AttrAckSignal                    => 263,
+    AttrControlPortOverNl80211       => 264,
+    AttrTxqStats                     => 265,
+    AttrTxqLimit                     => 266,
+    AttrTxqMemoryLimit               => 267,
+    AttrTxqQuantum                   => 268,
+    AttrHeCapability                 => 269,
+    AttrFtmResponder                 => 270,
+    AttrFtmResponderStats            => 271,
+    AttrTimeout                      => 272,
+    AttrPeerMeasurements             => 273,
+    AttrAirtimeWeight                => 274,
+    AttrStaTxPowerSetting            => 275,
+    AttrStaTxPower                   => 276,
+    AttrSaePassword                  => 277,
+    AttrTwtResponder                 => 278,
+    AttrHeObssPd                     => 279,
+    AttrWiphyEdmgChannels            => 280,
+    AttrWiphyEdmgBwConfig            => 281,
+    AttrVlanId                       => 282,
+    AttrHeBssColor                   => 283,
+    AttrIftypeAkmSuites              => 284,
+    AttrTidConfig                    => 285,
+    AttrControlPortNoPreauth         => 286,
+    AttrPmkLifetime                  => 287,
+    AttrPmkReauthThreshold           => 288,
+    AttrReceiveMulticast             => 289,
+    AttrWiphyFreqOffset              => 290,
+    AttrCenterFreq1Offset            => 291,
+    AttrScanFreqKhz                  => 292,
+    AttrHe6ghzCapability             => 293,
+    AttrFilsDiscovery                => 294,
+    AttrUnsolBcastProbeResp          => 295,
+    AttrS1gCapability                => 296,
+    AttrS1gCapabilityMask            => 297,
+    AttrSaePwe                       => 298,
+    AttrAfterLast                    => 299,
+    NumAttr                          => 299,//__AttrAfterLast,
+    AttrMax                          => 298//__AttrAfterLast - 1
 );
 
 impl fmt::Display for Nl80211Attr {
@@ -330,8 +387,18 @@ impl_var_trait!(
     RateInfo160MhzWidth   => 10,
     RateInfo10MhzWidth    => 11,
     RateInfo5MhzWidth     => 12,
-    RateInfoAfterLast     => 13,
-    RateInfoMax           => 12
+    RateInfoHeMcs         => 13,
+    RateInfoHeNss         => 14,
+    RateInfoHeGi          => 15,
+    RateInfoHeDcm         => 16,
+    RateInfoHeRuAlloc     => 17,
+    RateInfo320MhzWidth   => 18,
+    RateInfoEhtMcs        => 19,
+    RateInfoEhtNss        => 20,
+    RateInfoEhtGi         => 21,
+    RateInfoEhtRuAlloc    => 22,
+    RateInfoAfterLast     => 23,
+    RateInfoMax           => 22
 );
 
 impl_var_trait!(
@@ -592,19 +659,21 @@ impl_var_trait!(
     ///
     /// Enumeration from nl80211/nl80211.h:3118
     Nl80211SurveyInfo, u16, NlAttrType,
-    SurveyInfoInvalid     => 0,
-    SurveyInfoFrequency   => 1,
-    SurveyInfoNoise       => 2,
-    SurveyInfoInUse       => 3,
-    SurveyInfoTime        => 4,
-    SurveyInfoTimeBusy    => 5,
-    SurveyInfoTimeExtBusy => 6,
-    SurveyInfoTimeRx      => 7,
-    SurveyInfoTimeTx      => 8,
-    SurveyInfoTimeScan    => 9,
-    SurveyInfoPad         => 10,
-    SurveyInfoAfterLast   => 11,
-    SurveyInfoMax         => 10
+    SurveyInfoInvalid         => 0,
+    SurveyInfoFrequency       => 1,
+    SurveyInfoNoise           => 2,
+    SurveyInfoInUse           => 3,
+    SurveyInfoTime            => 4,
+    SurveyInfoTimeBusy        => 5,
+    SurveyInfoTimeExtBusy     => 6,
+    SurveyInfoTimeRx          => 7,
+    SurveyInfoTimeTx          => 8,
+    SurveyInfoTimeScan        => 9,
+    SurveyInfoPad             => 10,
+    SurveyInfoTimeBssRx       => 11,
+    SurveyInfoFrequencyOffset => 12,
+    SurveyInfoAfterLast       => 13,
+    SurveyInfoMax             => 12
 );
 
 impl_var_trait!(
@@ -742,7 +811,13 @@ impl_var_trait!(
     ChanWidth80p80  => 4,
     ChanWidth160    => 5,
     ChanWidth5      => 6,
-    ChanWidth10     => 7
+    ChanWidth10     => 7,
+    ChanWidth1      => 8,
+    ChanWidth2      => 9,
+    ChanWidth4      => 10,
+    ChanWidth8      => 11,
+    ChanWidth16     => 12,
+    ChanWidth320    => 13
 );
 
 impl_var_trait!(
@@ -924,8 +999,9 @@ impl_var_trait!(
     AttrCqmTxePkts            => 6,
     AttrCqmTxeIntvl           => 7,
     AttrCqmBeaconLossEvent    => 8,
-    AttrCqmAfterLast          => 9,
-    AttrCqmMax                => 8
+    AttrCqmRssiLevel          => 9,
+    AttrCqmAfterLast          => 10,
+    AttrCqmMax                => 9
 );
 
 impl_var_trait!(
@@ -1148,11 +1224,11 @@ impl_var_trait!(
     TdlsDisableLink  => 4
 );
 
-impl_var_trait!(
+impl_var!(
     /// nl80211FeatureFlags
     ///
     /// Enumeration from nl80211/nl80211.h:4526
-    Nl80211FeatureFlags, u16, NlAttrType,
+    Nl80211FeatureFlags, u32,
     FeatureSkTxStatus                => 1 << 0,
     FeatureHtIbss                    => 1 << 1,
     FeatureInactivityTimer           => 1 << 2,
@@ -1167,23 +1243,23 @@ impl_var_trait!(
     FeatureP2pGoCtwin                => 1 << 11,
     FeatureP2pGoOppps                => 1 << 12,
     FeatureAdvertiseChanLimits       => 1 << 14,
-    FeatureFullApClientState         => 1 << 15
-    // FeatureUserspaceMpm           => 1 << 16,
-    // FeatureActiveMonitor          => 1 << 17,
-    // FeatureApModeChanWidthChange  => 1 << 18,
-    // FeatureDsParamSetIeInProbes   => 1 << 19,
-    // FeatureWfaTpcIeInProbes       => 1 << 20,
-    // FeatureQuiet                  => 1 << 21,
-    // FeatureTxPowerInsertion       => 1 << 22,
-    // FeatureAcktoEstimation        => 1 << 23,
-    // FeatureStaticSmps             => 1 << 24,
-    // FeatureDynamicSmps            => 1 << 25,
-    // FeatureSupportsWmmAdmission   => 1 << 26,
-    // FeatureMacOnCreate            => 1 << 27,
-    // FeatureTdlsChannelSwitch      => 1 << 28,
-    // FeatureScanRandomMacAddr      => 1 << 29,
-    // FeatureSchedScanRandomMacAddr => 1 << 30,
-    // FeatureNdRandomMacAddr        => 1 << 31
+    FeatureFullApClientState         => 1 << 15,
+    FeatureUserspaceMpm              => 1 << 16,
+    FeatureActiveMonitor             => 1 << 17,
+    FeatureApModeChanWidthChange     => 1 << 18,
+    FeatureDsParamSetIeInProbes      => 1 << 19,
+    FeatureWfaTpcIeInProbes          => 1 << 20,
+    FeatureQuiet                     => 1 << 21,
+    FeatureTxPowerInsertion          => 1 << 22,
+    FeatureAcktoEstimation           => 1 << 23,
+    FeatureStaticSmps                => 1 << 24,
+    FeatureDynamicSmps               => 1 << 25,
+    FeatureSupportsWmmAdmission      => 1 << 26,
+    FeatureMacOnCreate               => 1 << 27,
+    FeatureTdlsChannelSwitch         => 1 << 28,
+    FeatureScanRandomMacAddr         => 1 << 29,
+    FeatureSchedScanRandomMacAddr    => 1 << 30,
+    FeatureNdRandomMacAddr           => 1 << 31
 );
 
 impl_var_trait!(
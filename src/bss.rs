@@ -1,10 +1,11 @@
 use crate::attr::Nl80211Attr;
 use crate::attr::Nl80211Bss;
+use crate::error::Error;
 use crate::helpers::parse_macaddr;
+use crate::ie::{parse_information_elements, InformationElement};
 use crate::nl80211traits::FromNlAttributeHandle;
 use byteorder::{LittleEndian, ReadBytesExt};
 use macaddr::MacAddr;
-use neli::err::NlError;
 use neli::nlattr::AttrHandle;
 use std::fmt;
 
@@ -22,12 +23,22 @@ pub struct Bss {
     pub status: Option<bool>,
     /// Signal strength of probe response/beacon in mBm (100 * dBm)
     pub signal: Option<i32>,
+    /// Network name, taken from the SSID information element
+    pub ssid: Option<String>,
+    /// Information elements of the last received frame (beacon or probe response)
+    pub information_elements: Option<Vec<InformationElement>>,
+    /// Information elements of the last received beacon
+    pub beacon_ies: Option<Vec<InformationElement>>,
 }
 
 impl fmt::Display for Bss {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut result = Vec::new();
 
+        if let Some(ssid) = &self.ssid {
+            result.push(format!("ssid : {}", ssid))
+        };
+
         if let Some(bssid) = &self.bssid {
             result.push(format!("bssid : {}", bssid))
         };
@@ -58,13 +69,11 @@ impl fmt::Display for Bss {
 
 impl FromNlAttributeHandle for Bss {
     /// Parse netlink messages returned by the nl80211 command CmdGetScan
-    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Bss, NlError> {
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Bss, Error> {
         let mut bss = Bss {
             ..Default::default()
         };
         for attr in handle.iter() {
-            println!("{:?}", attr);
-
             if attr.nla_type != Nl80211Attr::AttrBss {
                 continue;
             }
@@ -89,10 +98,28 @@ impl FromNlAttributeHandle for Bss {
                     Nl80211Bss::BssSignalMbm => {
                         bss.signal = Some(payload.read_i32::<LittleEndian>()?)
                     }
+                    Nl80211Bss::BssInformationElements => {
+                        bss.information_elements =
+                            Some(parse_information_elements(&sub_attr.payload))
+                    }
+                    Nl80211Bss::BssBeaconIes => {
+                        bss.beacon_ies = Some(parse_information_elements(&sub_attr.payload))
+                    }
                     _ => (),
                 }
             }
         }
+
+        bss.ssid = bss
+            .information_elements
+            .iter()
+            .chain(bss.beacon_ies.iter())
+            .flatten()
+            .find_map(|element| match element {
+                InformationElement::Ssid(ssid) => Some(ssid.clone()),
+                _ => None,
+            });
+
         Ok(bss)
     }
 }
@@ -101,6 +128,7 @@ impl FromNlAttributeHandle for Bss {
 mod test_bss {
     use super::*;
     use crate::attr::Nl80211Attr::*;
+    use crate::ie::{AkmSuite, CipherSuite, Rsn};
     use neli::nlattr::Nlattr;
 
     #[test]
@@ -112,9 +140,13 @@ mod test_bss {
             seen_ms_ago: Some(100),
             status: Some(true),
             signal: Some(-5300),
+            ssid: Some("eduroam".into()),
+            information_elements: None,
+            beacon_ies: None,
         };
 
-        let expected_output = r#"bssid : FF:FF:FF:FF:FF:FF
+        let expected_output = r#"ssid : eduroam
+        bssid : FF:FF:FF:FF:FF:FF
         frequency : 2.412 Ghz
         beacon interval : 100 TUs
         last seen : 100 ms
@@ -195,8 +227,23 @@ mod test_bss {
             seen_ms_ago: Some(100),
             status: Some(true),
             signal: Some(-5300),
+            ssid: Some("SFR-1c28".into()),
+            information_elements: bss.information_elements.clone(),
+            beacon_ies: bss.beacon_ies.clone(),
         };
 
-        assert_eq!(bss, expected_bss)
+        assert_eq!(bss, expected_bss);
+
+        let ies = bss.information_elements.unwrap();
+        assert_eq!(ies.len(), 18);
+        assert_eq!(ies[2], InformationElement::DsParameterSet(1));
+        assert!(ies.contains(&InformationElement::Rsn(Rsn {
+            version: 1,
+            group_cipher: Some(CipherSuite::Tkip),
+            pairwise_ciphers: vec![CipherSuite::Ccmp, CipherSuite::Tkip],
+            akm_suites: vec![AkmSuite::Psk],
+            capabilities: Some(12),
+        })));
+        assert_eq!(bss.beacon_ies.unwrap().len(), 19);
     }
 }
@@ -0,0 +1,218 @@
+use crate::attr::{Nl80211Attr, Nl80211ChanWidth, Nl80211ChannelType};
+use crate::error::Error;
+use crate::nl80211traits::ToNlAttributes;
+use neli::nlattr::Nlattr;
+use std::fmt;
+
+/// A frequency band, used to convert channel numbers to frequencies
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FrequencyBand {
+    /// 2.4 GHz band
+    Band2GHz,
+    /// 5 GHz band (including the 4.9 GHz channels of Japan)
+    Band5GHz,
+    /// 6 GHz band
+    Band6GHz,
+    /// 60 GHz band
+    Band60GHz,
+}
+
+/// Get the channel number of a frequency in MHz
+pub fn frequency_to_channel(frequency: u32) -> Option<u32> {
+    match frequency {
+        2484 => Some(14),
+        5935 => Some(2),
+        2412..=2472 => Some((frequency - 2407) / 5),
+        4910..=4980 => Some((frequency - 4000) / 5),
+        5000..=5920 => Some((frequency - 5000) / 5),
+        5950..=7115 => Some((frequency - 5950) / 5),
+        58320..=70200 => Some((frequency - 56160) / 2160),
+        _ => None,
+    }
+}
+
+/// Get the frequency in MHz of a channel number
+pub fn channel_to_frequency(channel: u32, band: FrequencyBand) -> Option<u32> {
+    match (band, channel) {
+        (FrequencyBand::Band2GHz, 14) => Some(2484),
+        (FrequencyBand::Band2GHz, 1..=13) => Some(2407 + channel * 5),
+        (FrequencyBand::Band5GHz, 182..=196) => Some(4000 + channel * 5),
+        (FrequencyBand::Band5GHz, 1..=181) => Some(5000 + channel * 5),
+        (FrequencyBand::Band6GHz, 2) => Some(5935),
+        (FrequencyBand::Band6GHz, 1..=233) => Some(5950 + channel * 5),
+        (FrequencyBand::Band60GHz, 1..=6) => Some(56160 + channel * 2160),
+        _ => None,
+    }
+}
+
+/// The channel an interface operates on
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelDef {
+    /// Frequency of the control channel in MHz
+    pub frequency: u32,
+    /// Channel width
+    pub width: Option<Nl80211ChanWidth>,
+    /// Center frequency of the first segment in MHz
+    pub center_freq1: Option<u32>,
+    /// Center frequency of the second segment in MHz (80+80 MHz channels only)
+    pub center_freq2: Option<u32>,
+    /// Legacy HT channel type
+    pub channel_type: Option<Nl80211ChannelType>,
+}
+
+impl ChannelDef {
+    /// Channel number of the control channel
+    pub fn channel(&self) -> Option<u32> {
+        frequency_to_channel(self.frequency)
+    }
+
+    /// Channel width in MHz (width of each segment for 80+80 MHz channels)
+    pub fn width_mhz(&self) -> Option<u32> {
+        match self.width.as_ref()? {
+            Nl80211ChanWidth::ChanWidth20Noht | Nl80211ChanWidth::ChanWidth20 => Some(20),
+            Nl80211ChanWidth::ChanWidth40 => Some(40),
+            Nl80211ChanWidth::ChanWidth80 | Nl80211ChanWidth::ChanWidth80p80 => Some(80),
+            Nl80211ChanWidth::ChanWidth160 => Some(160),
+            Nl80211ChanWidth::ChanWidth5 => Some(5),
+            Nl80211ChanWidth::ChanWidth10 => Some(10),
+            Nl80211ChanWidth::ChanWidth1 => Some(1),
+            Nl80211ChanWidth::ChanWidth2 => Some(2),
+            Nl80211ChanWidth::ChanWidth4 => Some(4),
+            Nl80211ChanWidth::ChanWidth8 => Some(8),
+            Nl80211ChanWidth::ChanWidth16 => Some(16),
+            Nl80211ChanWidth::ChanWidth320 => Some(320),
+            Nl80211ChanWidth::UnrecognizedVariant(_) => None,
+        }
+    }
+}
+
+impl ToNlAttributes for ChannelDef {
+    /// Build the channel attributes of nl80211 commands such as CmdStartAp
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrWiphyFreq,
+            self.frequency.to_le_bytes().to_vec(),
+        )?];
+
+        if let Some(channel_type) = &self.channel_type {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphyChannelType,
+                u32::from(u16::from(channel_type)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(width) = &self.width {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrChannelWidth,
+                u32::from(u16::from(width)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(center_freq1) = self.center_freq1 {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrCenterFreq1,
+                center_freq1.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(center_freq2) = self.center_freq2 {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrCenterFreq2,
+                center_freq2.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+impl fmt::Display for ChannelDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.channel() {
+            Some(channel) => write!(f, "channel {} ({} MHz)", channel, self.frequency)?,
+            None => write!(f, "{} MHz", self.frequency)?,
+        }
+
+        match &self.width {
+            Some(Nl80211ChanWidth::ChanWidth20Noht) => write!(f, ", width: 20 MHz (no HT)")?,
+            Some(Nl80211ChanWidth::ChanWidth80p80) => write!(f, ", width: 80+80 MHz")?,
+            _ => {
+                if let Some(width) = self.width_mhz() {
+                    write!(f, ", width: {} MHz", width)?
+                }
+            }
+        }
+
+        if let Some(center_freq1) = self.center_freq1 {
+            write!(f, ", center1: {} MHz", center_freq1)?
+        }
+
+        if let Some(center_freq2) = self.center_freq2 {
+            write!(f, ", center2: {} MHz", center_freq2)?
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_channel {
+    use super::*;
+
+    #[test]
+    fn test_frequency_to_channel() {
+        assert_eq!(frequency_to_channel(2412), Some(1));
+        assert_eq!(frequency_to_channel(2484), Some(14));
+        assert_eq!(frequency_to_channel(5180), Some(36));
+        assert_eq!(frequency_to_channel(5955), Some(1));
+        assert_eq!(frequency_to_channel(60480), Some(2));
+        assert_eq!(frequency_to_channel(5920), Some(184));
+        // 5925 MHz is the lower edge of the 6 GHz band, not channel 185
+        assert_eq!(frequency_to_channel(5925), None);
+        assert_eq!(frequency_to_channel(1000), None);
+    }
+
+    #[test]
+    fn test_channel_to_frequency() {
+        assert_eq!(channel_to_frequency(1, FrequencyBand::Band2GHz), Some(2412));
+        assert_eq!(
+            channel_to_frequency(14, FrequencyBand::Band2GHz),
+            Some(2484)
+        );
+        assert_eq!(
+            channel_to_frequency(36, FrequencyBand::Band5GHz),
+            Some(5180)
+        );
+        assert_eq!(
+            channel_to_frequency(184, FrequencyBand::Band5GHz),
+            Some(4920)
+        );
+        assert_eq!(channel_to_frequency(1, FrequencyBand::Band6GHz), Some(5955));
+        assert_eq!(
+            channel_to_frequency(2, FrequencyBand::Band60GHz),
+            Some(60480)
+        );
+        assert_eq!(channel_to_frequency(36, FrequencyBand::Band2GHz), None);
+    }
+
+    #[test]
+    fn test_pretty_format() {
+        let channel_def = ChannelDef {
+            frequency: 5180,
+            width: Some(Nl80211ChanWidth::ChanWidth80),
+            center_freq1: Some(5210),
+            center_freq2: None,
+            channel_type: Some(Nl80211ChannelType::ChanNoHt),
+        };
+
+        assert_eq!(
+            format!("{}", channel_def),
+            "channel 36 (5180 MHz), width: 80 MHz, center1: 5210 MHz"
+        )
+    }
+}
@@ -0,0 +1,269 @@
+use crate::attr::{Nl80211Attr, Nl80211AuthType, Nl80211Mfp, Nl80211WpaVersions};
+use crate::error::Error;
+use crate::ie::{AkmSuite, CipherSuite};
+use crate::nl80211traits::ToNlAttributes;
+use macaddr::MacAddr;
+use neli::nlattr::Nlattr;
+
+/// Parameters of a connection handled by the in-kernel SME with the nl80211 command CmdConnect
+///
+/// The default parameters connect to an open network, the SSID must be set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectParams {
+    /// SSID of the network
+    pub ssid: String,
+    /// BSSID to prefer, the kernel may still pick another BSS of the network
+    pub bssid_hint: Option<MacAddr>,
+    /// Frequency to prefer in MHz
+    pub frequency_hint: Option<u32>,
+    /// Authentication algorithm, picked by the kernel when not set
+    pub auth_type: Option<Nl80211AuthType>,
+    /// WPA versions in use
+    pub wpa_versions: Vec<Nl80211WpaVersions>,
+    /// Pairwise cipher suites, the network is protected when a cipher suite is set
+    pub pairwise_ciphers: Vec<CipherSuite>,
+    /// Group cipher suite
+    pub group_cipher: Option<CipherSuite>,
+    /// Authentication and key management suites
+    pub akm_suites: Vec<AkmSuite>,
+    /// Management frame protection
+    pub mfp: Option<Nl80211Mfp>,
+    /// PMK for the drivers handling the 4-way handshake of WPA2-PSK networks
+    pub pmk: Option<Vec<u8>>,
+    /// Extra information elements added to the (re)association request
+    pub ies: Option<Vec<u8>>,
+}
+
+impl ToNlAttributes for ConnectParams {
+    /// Build the attributes of the nl80211 command CmdConnect
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrSsid,
+            self.ssid.as_bytes().to_vec(),
+        )?];
+
+        if let Some(bssid) = &self.bssid_hint {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMacHint,
+                bssid.as_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(frequency) = self.frequency_hint {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphyFreqHint,
+                frequency.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(auth_type) = &self.auth_type {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        attrs.extend(crypto_attrs(
+            true,
+            &self.wpa_versions,
+            &self.pairwise_ciphers,
+            self.group_cipher,
+            &self.akm_suites,
+        )?);
+
+        if let Some(mfp) = &self.mfp {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrUseMfp,
+                u32::from(u16::from(mfp)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(pmk) = &self.pmk {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrPmk, pmk.clone())?);
+        }
+
+        if let Some(ies) = &self.ies {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrIe, ies.clone())?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Build the crypto settings shared by the nl80211 commands CmdConnect, CmdAssociate and
+/// CmdStartAp
+///
+/// The privacy flag, unused by CmdAssociate, is set along with the cipher suites.
+pub(crate) fn crypto_attrs(
+    privacy: bool,
+    wpa_versions: &[Nl80211WpaVersions],
+    pairwise_ciphers: &[CipherSuite],
+    group_cipher: Option<CipherSuite>,
+    akm_suites: &[AkmSuite],
+) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+    let mut attrs = Vec::new();
+
+    if !wpa_versions.is_empty() {
+        let wpa_versions = wpa_versions
+            .iter()
+            .fold(0u32, |acc, version| acc | u32::from(u16::from(version)));
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrWpaVersions,
+            wpa_versions.to_le_bytes().to_vec(),
+        )?);
+    }
+
+    if privacy && (!pairwise_ciphers.is_empty() || group_cipher.is_some()) {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrPrivacy,
+            Vec::<u8>::new(),
+        )?);
+    }
+
+    if !pairwise_ciphers.is_empty() {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrCipherSuitesPairwise,
+            suites_payload(pairwise_ciphers.iter().map(|suite| suite.selector())),
+        )?);
+    }
+
+    if let Some(group_cipher) = group_cipher {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrCipherSuiteGroup,
+            group_cipher.selector().to_le_bytes().to_vec(),
+        )?);
+    }
+
+    if !akm_suites.is_empty() {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrAkmSuites,
+            suites_payload(akm_suites.iter().map(|suite| suite.selector())),
+        )?);
+    }
+
+    Ok(attrs)
+}
+
+/// Concatenate suite selectors as expected by nl80211
+fn suites_payload(selectors: impl Iterator<Item = u32>) -> Vec<u8> {
+    selectors
+        .flat_map(|selector| selector.to_le_bytes().to_vec())
+        .collect()
+}
+
+/// Result of a connection attempt reported by the nl80211 event CmdConnect
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectResult {
+    /// BSSID of the network
+    pub bssid: Option<MacAddr>,
+    /// IEEE 802.11 status code, 0 on success
+    pub status: Option<u16>,
+    /// Information elements of the (re)association request
+    pub req_ie: Option<Vec<u8>>,
+    /// Information elements of the (re)association response
+    pub resp_ie: Option<Vec<u8>>,
+}
+
+impl ConnectResult {
+    /// Whether the interface is connected
+    pub fn is_success(&self) -> bool {
+        self.status == Some(0)
+    }
+}
+
+#[cfg(test)]
+mod test_connect {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_open_network() {
+        let params = ConnectParams {
+            ssid: "eduroam".into(),
+            ..Default::default()
+        };
+
+        let expected_attrs = vec![Nlattr {
+            nla_len: 11,
+            nla_type: AttrSsid,
+            payload: vec![101, 100, 117, 114, 111, 97, 109],
+        }];
+
+        assert_eq!(params.to_attrs().unwrap(), expected_attrs)
+    }
+
+    #[test]
+    fn test_wpa2_psk_network() {
+        let params = ConnectParams {
+            ssid: "home".into(),
+            frequency_hint: Some(2412),
+            auth_type: Some(Nl80211AuthType::AuthtypeOpenSystem),
+            wpa_versions: vec![Nl80211WpaVersions::WpaVersion2],
+            pairwise_ciphers: vec![CipherSuite::Ccmp],
+            group_cipher: Some(CipherSuite::Ccmp),
+            akm_suites: vec![AkmSuite::Psk],
+            mfp: Some(Nl80211Mfp::MfpNo),
+            ..Default::default()
+        };
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrSsid,
+                payload: vec![104, 111, 109, 101],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphyFreqHint,
+                payload: vec![108, 9, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrAuthType,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWpaVersions,
+                payload: vec![2, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 4,
+                nla_type: AttrPrivacy,
+                payload: vec![],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrCipherSuitesPairwise,
+                payload: vec![4, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrCipherSuiteGroup,
+                payload: vec![4, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrAkmSuites,
+                payload: vec![2, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrUseMfp,
+                payload: vec![0, 0, 0, 0],
+            },
+        ];
+
+        assert_eq!(params.to_attrs().unwrap(), expected_attrs)
+    }
+}
@@ -1,2 +1,9 @@
 pub const NL_80211_GENL_NAME: &str = "nl80211";
 pub const NL_80211_GENL_VERSION: u8 = 1;
+pub const NL_80211_MULTICAST_GROUP_CONFIG: &str = "config";
+pub const NL_80211_MULTICAST_GROUP_SCAN: &str = "scan";
+pub const NL_80211_MULTICAST_GROUP_REG: &str = "regulatory";
+pub const NL_80211_MULTICAST_GROUP_MLME: &str = "mlme";
+pub const NL_80211_MULTICAST_GROUP_VENDOR: &str = "vendor";
+pub const NL_80211_MULTICAST_GROUP_NAN: &str = "nan";
+pub const NL_80211_MULTICAST_GROUP_TESTMODE: &str = "testmode";
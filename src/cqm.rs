@@ -0,0 +1,202 @@
+use crate::attr::{Nl80211Attr, Nl80211AttrCqm, Nl80211CqmRssiThresholdEvent};
+use crate::error::Error;
+use crate::nl80211traits::ToNlAttributes;
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::nlattr::{AttrHandle, Nlattr};
+
+/// Connection quality monitor configuration set with the nl80211 command CmdSetCqm
+///
+/// RSSI and TX error monitoring are configured separately. Beacon loss events are reported by
+/// the drivers supporting them while RSSI monitoring is enabled.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CqmConfig {
+    /// Report the RSSI crossing thresholds
+    Rssi {
+        /// Thresholds in dBm sorted from low to high, RSSI monitoring is disabled when empty.
+        /// Several thresholds are only accepted by drivers supporting RSSI lists.
+        thresholds: Vec<i32>,
+        /// Minimum change of the RSSI in dB between two events
+        hysteresis: u32,
+    },
+    /// Report TX errors
+    TxErrors {
+        /// Minimum percentage of TX failures
+        rate: u32,
+        /// Minimum number of packets sent during an interval
+        packets: u32,
+        /// Interval in seconds, TX error monitoring is disabled when 0
+        interval: u32,
+    },
+}
+
+impl ToNlAttributes for CqmConfig {
+    /// Build the attributes of the nl80211 command CmdSetCqm
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut cqm = Nlattr::new(None, Nl80211Attr::AttrCqm, Vec::<u8>::new())?;
+
+        match self {
+            CqmConfig::Rssi {
+                thresholds,
+                hysteresis,
+            } => {
+                // A single null threshold disables RSSI monitoring on every kernel version
+                let thresholds: Vec<u8> = match thresholds.is_empty() {
+                    true => vec![0; 4],
+                    false => thresholds
+                        .iter()
+                        .flat_map(|threshold| threshold.to_le_bytes().to_vec())
+                        .collect(),
+                };
+                cqm.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211AttrCqm::AttrCqmRssiThold,
+                    thresholds,
+                )?)?;
+                cqm.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211AttrCqm::AttrCqmRssiHyst,
+                    hysteresis.to_le_bytes().to_vec(),
+                )?)?;
+            }
+            CqmConfig::TxErrors {
+                rate,
+                packets,
+                interval,
+            } => {
+                cqm.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211AttrCqm::AttrCqmTxeRate,
+                    rate.to_le_bytes().to_vec(),
+                )?)?;
+                cqm.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211AttrCqm::AttrCqmTxePkts,
+                    packets.to_le_bytes().to_vec(),
+                )?)?;
+                cqm.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211AttrCqm::AttrCqmTxeIntvl,
+                    interval.to_le_bytes().to_vec(),
+                )?)?;
+            }
+        }
+
+        Ok(vec![cqm])
+    }
+}
+
+/// Connection quality notification carried by the nl80211 event CmdNotifyCqm
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CqmEvent {
+    /// The RSSI went below or above a threshold, or beacons were lost
+    pub rssi_threshold_event: Option<Nl80211CqmRssiThresholdEvent>,
+    /// RSSI in dBm which triggered the RSSI threshold event
+    pub rssi_level: Option<i32>,
+    /// Number of consecutive packets not acknowledged by the peer
+    pub packet_loss: Option<u32>,
+    /// Percentage of TX failures which triggered the TX error event
+    pub txe_rate: Option<u32>,
+    /// Number of packets sent during the TX error interval
+    pub txe_packets: Option<u32>,
+    /// TX error interval in seconds
+    pub txe_interval: Option<u32>,
+    /// Beacons of the access point were lost
+    pub beacon_loss: bool,
+}
+
+impl CqmEvent {
+    /// Parse the nested attribute AttrCqm of a notification
+    pub(crate) fn from_handle(handle: AttrHandle<Nl80211AttrCqm>) -> Result<CqmEvent, Error> {
+        let mut event = CqmEvent::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211AttrCqm::AttrCqmRssiThresholdEvent => {
+                    event.rssi_threshold_event = Some(Nl80211CqmRssiThresholdEvent::from(
+                        payload.read_u32::<LittleEndian>()? as u16,
+                    ))
+                }
+                Nl80211AttrCqm::AttrCqmRssiLevel => {
+                    event.rssi_level = Some(payload.read_i32::<LittleEndian>()?)
+                }
+                Nl80211AttrCqm::AttrCqmPktLossEvent => {
+                    event.packet_loss = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211AttrCqm::AttrCqmTxeRate => {
+                    event.txe_rate = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211AttrCqm::AttrCqmTxePkts => {
+                    event.txe_packets = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211AttrCqm::AttrCqmTxeIntvl => {
+                    event.txe_interval = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211AttrCqm::AttrCqmBeaconLossEvent => event.beacon_loss = true,
+                _ => (),
+            }
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod test_cqm {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_rssi_config_to_attrs() {
+        let config = CqmConfig::Rssi {
+            thresholds: vec![-80, -70],
+            hysteresis: 4,
+        };
+
+        let expected_attrs = vec![Nlattr {
+            nla_len: 24,
+            nla_type: AttrCqm,
+            payload: vec![
+                12, 0, 1, 0, 176, 255, 255, 255, 186, 255, 255, 255, 8, 0, 2, 0, 4, 0, 0, 0,
+            ],
+        }];
+
+        assert_eq!(config.to_attrs().unwrap(), expected_attrs);
+
+        let disabled = CqmConfig::Rssi {
+            thresholds: vec![],
+            hysteresis: 0,
+        };
+
+        assert_eq!(
+            disabled.to_attrs().unwrap()[0].payload,
+            vec![8, 0, 1, 0, 0, 0, 0, 0, 8, 0, 2, 0, 0, 0, 0, 0]
+        )
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: Nl80211AttrCqm::AttrCqmRssiThresholdEvent,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: Nl80211AttrCqm::AttrCqmRssiLevel,
+                payload: vec![174, 255, 255, 255],
+            },
+        ];
+
+        let event = CqmEvent::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        let expected_event = CqmEvent {
+            rssi_threshold_event: Some(Nl80211CqmRssiThresholdEvent::CqmRssiThresholdEventLow),
+            rssi_level: Some(-82),
+            ..Default::default()
+        };
+
+        assert_eq!(event, expected_event)
+    }
+}
@@ -0,0 +1,235 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::err::{DeError, NlError, SerError};
+use std::fmt;
+use std::io;
+
+/// Netlink message header length (struct nlmsghdr)
+const NLMSG_HDRLEN: usize = 16;
+
+/// Flag set on error messages whose request payload has been omitted (NLM_F_CAPPED)
+const NLM_F_CAPPED: u16 = 0x100;
+
+/// Flag set on error messages carrying extended ACK attributes (NLM_F_ACK_TLVS)
+const NLM_F_ACK_TLVS: u16 = 0x200;
+
+/// Extended ACK attribute holding a human readable error message (NLMSGERR_ATTR_MSG)
+const NLMSGERR_ATTR_MSG: u16 = 1;
+
+/// Extended ACK attribute holding the offset of the invalid attribute (NLMSGERR_ATTR_OFFS)
+const NLMSGERR_ATTR_OFFS: u16 = 2;
+
+/// Errors returned by this crate
+#[derive(Debug)]
+pub enum Error {
+    /// The kernel rejected the request
+    Kernel(KernelError),
+    /// An attribute returned by nl80211 could not be decoded
+    Parse(String),
+    /// A request could not be built from the given parameters
+    Invalid(String),
+    /// The netlink socket failed to send or receive a message
+    Netlink(NlError),
+}
+
+impl Error {
+    /// Kernel errno (positive value) if the kernel rejected the request
+    pub fn errno(&self) -> Option<i32> {
+        match self {
+            Error::Kernel(err) => Some(err.errno),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Kernel(err) => write!(f, "{}", err),
+            Error::Parse(msg) => write!(f, "Unable to parse nl80211 attributes: {}", msg),
+            Error::Invalid(msg) => write!(f, "Invalid request: {}", msg),
+            Error::Netlink(err) => write!(f, "Netlink error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Kernel(err) => Some(err),
+            Error::Netlink(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<KernelError> for Error {
+    fn from(err: KernelError) -> Self {
+        Error::Kernel(err)
+    }
+}
+
+impl From<NlError> for Error {
+    fn from(err: NlError) -> Self {
+        Error::Netlink(err)
+    }
+}
+
+impl From<SerError> for Error {
+    fn from(err: SerError) -> Self {
+        Error::Netlink(err.into())
+    }
+}
+
+/// Attribute payloads are decoded with `neli` deserializers
+impl From<DeError> for Error {
+    fn from(err: DeError) -> Self {
+        Error::Parse(err.to_string())
+    }
+}
+
+/// Attribute payloads are read with `byteorder`, which fails with an io error on short payloads
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Parse(err.to_string())
+    }
+}
+
+/// An error message sent back by the kernel in response to a request
+#[derive(Clone, Debug, PartialEq)]
+pub struct KernelError {
+    /// Error number (EPERM, ENODEV, EBUSY...) as a positive value
+    pub errno: i32,
+    /// Extended ACK message explaining the error
+    pub message: Option<String>,
+    /// Offset in the request of the attribute that caused the error
+    pub offset: Option<u32>,
+}
+
+impl KernelError {
+    /// Parse the payload of a netlink error message (struct nlmsgerr followed by extended ACK attributes)
+    pub(crate) fn from_payload(flags: u16, payload: &[u8]) -> Result<KernelError, Error> {
+        let mut reader = payload;
+        let errno = -reader.read_i32::<LittleEndian>()?;
+
+        let mut error = KernelError {
+            errno,
+            message: None,
+            offset: None,
+        };
+
+        if flags & NLM_F_ACK_TLVS == 0 {
+            return Ok(error);
+        }
+
+        let request_len = if flags & NLM_F_CAPPED != 0 {
+            NLMSG_HDRLEN
+        } else {
+            align((&payload[4..]).read_u32::<LittleEndian>()? as usize)
+        };
+
+        let mut tlvs = payload.get(4 + request_len..).unwrap_or(&[]);
+        while tlvs.len() >= 4 {
+            let mut header = tlvs;
+            let len = header.read_u16::<LittleEndian>()? as usize;
+            let kind = header.read_u16::<LittleEndian>()?;
+            if len < 4 || len > tlvs.len() {
+                break;
+            }
+
+            let mut value = &tlvs[4..len];
+            match kind {
+                NLMSGERR_ATTR_MSG => {
+                    error.message = Some(crate::helpers::parse_string(value));
+                }
+                NLMSGERR_ATTR_OFFS => {
+                    error.offset = Some(value.read_u32::<LittleEndian>()?);
+                }
+                _ => (),
+            }
+
+            tlvs = tlvs.get(align(len)..).unwrap_or(&[]);
+        }
+
+        Ok(error)
+    }
+
+    /// Get the errno as a standard io error
+    pub fn as_io_error(&self) -> io::Error {
+        io::Error::from_raw_os_error(self.errno)
+    }
+}
+
+impl fmt::Display for KernelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_io_error())?;
+
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " (attribute at offset {})", offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for KernelError {}
+
+/// Round a length up to the netlink 4 bytes alignment
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(test)]
+mod test_error {
+    use super::*;
+
+    #[test]
+    fn test_parse_errno() {
+        let payload = vec![
+            240, 255, 255, 255, 20, 0, 0, 0, 28, 0, 5, 3, 1, 0, 0, 0, 0, 0, 0, 0, 5, 1, 0, 0,
+        ];
+        let error = KernelError::from_payload(0, &payload).unwrap();
+
+        assert_eq!(
+            error,
+            KernelError {
+                errno: 16,
+                message: None,
+                offset: None,
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_extended_ack() {
+        let payload = vec![
+            234, 255, 255, 255, 28, 0, 0, 0, 28, 0, 5, 0, 1, 0, 0, 0, 0, 0, 0, 0, 15, 0, 1, 0, 98,
+            97, 100, 32, 105, 102, 116, 121, 112, 101, 0, 0, 8, 0, 2, 0, 24, 0, 0, 0,
+        ];
+        let error = KernelError::from_payload(NLM_F_CAPPED | NLM_F_ACK_TLVS, &payload).unwrap();
+
+        assert_eq!(
+            error,
+            KernelError {
+                errno: 22,
+                message: Some("bad iftype".into()),
+                offset: Some(24),
+            }
+        )
+    }
+
+    #[test]
+    fn test_errno() {
+        let error = Error::from(KernelError {
+            errno: 1,
+            message: None,
+            offset: None,
+        });
+
+        assert_eq!(error.errno(), Some(1));
+        assert_eq!(Error::Parse("short payload".into()).errno(), None);
+    }
+}
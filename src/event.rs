@@ -0,0 +1,423 @@
+use crate::attr::Nl80211Attr;
+use crate::cmd::Nl80211Cmd;
+use crate::consts::*;
+use crate::cqm::CqmEvent;
+use crate::error::Error;
+use crate::helpers::{parse_macaddr, parse_string};
+use crate::interface::Interface;
+use crate::nl80211traits::FromNlAttributeHandle;
+use byteorder::{LittleEndian, ReadBytesExt};
+use macaddr::MacAddr;
+use neli::nlattr::AttrHandle;
+
+/// A nl80211 multicast group broadcasting notifications of the kernel
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MulticastGroup {
+    /// Interface and wiphy configuration changes
+    Config,
+    /// Scan notifications (scan started, results available, scan aborted)
+    Scan,
+    /// Regulatory domain changes
+    Regulatory,
+    /// Authentication, association, connection and link quality notifications
+    Mlme,
+    /// Vendor specific notifications
+    Vendor,
+    /// Neighbor Awareness Networking notifications
+    Nan,
+    /// Test mode notifications
+    Testmode,
+}
+
+impl MulticastGroup {
+    /// Every nl80211 multicast group
+    pub const ALL: [MulticastGroup; 7] = [
+        MulticastGroup::Config,
+        MulticastGroup::Scan,
+        MulticastGroup::Regulatory,
+        MulticastGroup::Mlme,
+        MulticastGroup::Vendor,
+        MulticastGroup::Nan,
+        MulticastGroup::Testmode,
+    ];
+
+    /// Name of the group registered by nl80211 to the generic netlink controller
+    pub fn name(self) -> &'static str {
+        match self {
+            MulticastGroup::Config => NL_80211_MULTICAST_GROUP_CONFIG,
+            MulticastGroup::Scan => NL_80211_MULTICAST_GROUP_SCAN,
+            MulticastGroup::Regulatory => NL_80211_MULTICAST_GROUP_REG,
+            MulticastGroup::Mlme => NL_80211_MULTICAST_GROUP_MLME,
+            MulticastGroup::Vendor => NL_80211_MULTICAST_GROUP_VENDOR,
+            MulticastGroup::Nan => NL_80211_MULTICAST_GROUP_NAN,
+            MulticastGroup::Testmode => NL_80211_MULTICAST_GROUP_TESTMODE,
+        }
+    }
+}
+
+/// A notification sent by nl80211 to the members of a multicast group
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// An interface has been created or changed
+    NewInterface(Interface),
+    /// An interface has been deleted
+    DelInterface(Interface),
+    /// A scan has been started
+    TriggerScan { ifindex: Option<u32> },
+    /// Scan results are available
+    NewScanResults { ifindex: Option<u32> },
+    /// A scan has been aborted before its end
+    ScanAborted { ifindex: Option<u32> },
+    /// An authentication frame was received, or the authentication timed out
+    Authenticate {
+        ifindex: Option<u32>,
+        bssid: Option<MacAddr>,
+        frame: Option<Vec<u8>>,
+        timed_out: bool,
+    },
+    /// An association response was received, or the association timed out
+    Associate {
+        ifindex: Option<u32>,
+        bssid: Option<MacAddr>,
+        frame: Option<Vec<u8>>,
+        timed_out: bool,
+    },
+    /// A deauthentication frame was sent or received
+    Deauthenticate {
+        ifindex: Option<u32>,
+        frame: Option<Vec<u8>>,
+    },
+    /// A disassociation frame was sent or received
+    Disassociate {
+        ifindex: Option<u32>,
+        frame: Option<Vec<u8>>,
+    },
+    /// A connection attempt ended, the connection succeeded when status is 0
+    Connect {
+        ifindex: Option<u32>,
+        bssid: Option<MacAddr>,
+        status: Option<u16>,
+        req_ie: Option<Vec<u8>>,
+        resp_ie: Option<Vec<u8>>,
+    },
+    /// The interface has been disconnected
+    Disconnect {
+        ifindex: Option<u32>,
+        reason: Option<u16>,
+        by_ap: bool,
+    },
+    /// The regulatory domain changed
+    RegChange { alpha2: Option<String> },
+    /// The link quality crossed a configured threshold, `mac` being the peer of packet loss and
+    /// TX error events
+    NotifyCqm {
+        ifindex: Option<u32>,
+        mac: Option<MacAddr>,
+        cqm: CqmEvent,
+    },
+    /// Any other notification
+    Other {
+        cmd: Nl80211Cmd,
+        ifindex: Option<u32>,
+    },
+}
+
+impl Event {
+    /// Decode a notification from its nl80211 command and attributes
+    pub fn from_message(cmd: Nl80211Cmd, handle: AttrHandle<Nl80211Attr>) -> Result<Event, Error> {
+        let ifindex = match handle.get_attribute(Nl80211Attr::AttrIfindex) {
+            Some(attr) => Some((&attr.payload[..]).read_u32::<LittleEndian>()?),
+            None => None,
+        };
+
+        let event = match cmd {
+            Nl80211Cmd::CmdNewInterface => Event::NewInterface(Interface::from_handle(handle)?),
+            Nl80211Cmd::CmdDelInterface => Event::DelInterface(Interface::from_handle(handle)?),
+            Nl80211Cmd::CmdTriggerScan => Event::TriggerScan { ifindex },
+            Nl80211Cmd::CmdNewScanResults => Event::NewScanResults { ifindex },
+            Nl80211Cmd::CmdScanAborted => Event::ScanAborted { ifindex },
+            Nl80211Cmd::CmdAuthenticate | Nl80211Cmd::CmdAssociate => {
+                let mut bssid = None;
+                let mut frame = None;
+                let mut timed_out = false;
+                for attr in handle.iter() {
+                    match attr.nla_type {
+                        Nl80211Attr::AttrMac => bssid = Some(parse_macaddr(&attr.payload)?),
+                        Nl80211Attr::AttrFrame => frame = Some(attr.payload.clone()),
+                        Nl80211Attr::AttrTimedOut => timed_out = true,
+                        _ => (),
+                    }
+                }
+                match cmd {
+                    Nl80211Cmd::CmdAuthenticate => Event::Authenticate {
+                        ifindex,
+                        bssid,
+                        frame,
+                        timed_out,
+                    },
+                    _ => Event::Associate {
+                        ifindex,
+                        bssid,
+                        frame,
+                        timed_out,
+                    },
+                }
+            }
+            Nl80211Cmd::CmdDeauthenticate => Event::Deauthenticate {
+                ifindex,
+                frame: frame(&handle),
+            },
+            Nl80211Cmd::CmdDisassociate => Event::Disassociate {
+                ifindex,
+                frame: frame(&handle),
+            },
+            Nl80211Cmd::CmdConnect => {
+                let mut bssid = None;
+                let mut status = None;
+                let mut req_ie = None;
+                let mut resp_ie = None;
+                for attr in handle.iter() {
+                    match attr.nla_type {
+                        Nl80211Attr::AttrMac => bssid = Some(parse_macaddr(&attr.payload)?),
+                        Nl80211Attr::AttrStatusCode => {
+                            status = Some((&attr.payload[..]).read_u16::<LittleEndian>()?)
+                        }
+                        Nl80211Attr::AttrReqIe => req_ie = Some(attr.payload.clone()),
+                        Nl80211Attr::AttrRespIe => resp_ie = Some(attr.payload.clone()),
+                        _ => (),
+                    }
+                }
+                Event::Connect {
+                    ifindex,
+                    bssid,
+                    status,
+                    req_ie,
+                    resp_ie,
+                }
+            }
+            Nl80211Cmd::CmdDisconnect => {
+                let mut reason = None;
+                let mut by_ap = false;
+                for attr in handle.iter() {
+                    match attr.nla_type {
+                        Nl80211Attr::AttrReasonCode => {
+                            reason = Some((&attr.payload[..]).read_u16::<LittleEndian>()?)
+                        }
+                        Nl80211Attr::AttrDisconnectedByAp => by_ap = true,
+                        _ => (),
+                    }
+                }
+                Event::Disconnect {
+                    ifindex,
+                    reason,
+                    by_ap,
+                }
+            }
+            Nl80211Cmd::CmdRegChange => Event::RegChange {
+                alpha2: handle
+                    .get_attribute(Nl80211Attr::AttrRegAlpha2)
+                    .map(|attr| parse_string(&attr.payload)),
+            },
+            Nl80211Cmd::CmdNotifyCqm => {
+                let mut mac = None;
+                let mut cqm = CqmEvent::default();
+                for attr in handle.iter() {
+                    match attr.nla_type {
+                        Nl80211Attr::AttrMac => mac = Some(parse_macaddr(&attr.payload)?),
+                        Nl80211Attr::AttrCqm => {
+                            cqm = CqmEvent::from_handle(attr.get_nested_attributes()?)?
+                        }
+                        _ => (),
+                    }
+                }
+                Event::NotifyCqm { ifindex, mac, cqm }
+            }
+            cmd => Event::Other { cmd, ifindex },
+        };
+
+        Ok(event)
+    }
+}
+
+/// Get the management frame carried by a notification
+fn frame(handle: &AttrHandle<Nl80211Attr>) -> Option<Vec<u8>> {
+    handle
+        .get_attribute(Nl80211Attr::AttrFrame)
+        .map(|attr| attr.payload.clone())
+}
+
+#[cfg(test)]
+mod test_event {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::nlattr::Nlattr;
+
+    #[test]
+    fn test_group_names() {
+        let names: Vec<&str> = MulticastGroup::ALL
+            .iter()
+            .map(|group| group.name())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "config",
+                "scan",
+                "regulatory",
+                "mlme",
+                "vendor",
+                "nan",
+                "testmode"
+            ]
+        )
+    }
+
+    #[test]
+    fn test_parse_disconnect() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphy,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 6,
+                nla_type: AttrReasonCode,
+                payload: vec![3, 0],
+            },
+            Nlattr {
+                nla_len: 4,
+                nla_type: AttrDisconnectedByAp,
+                payload: vec![],
+            },
+        ];
+
+        let event =
+            Event::from_message(Nl80211Cmd::CmdDisconnect, AttrHandle::Owned(handler)).unwrap();
+
+        assert_eq!(
+            event,
+            Event::Disconnect {
+                ifindex: Some(3),
+                reason: Some(3),
+                by_ap: true,
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_cqm_beacon_loss() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrCqm,
+                payload: vec![4, 0, 8, 0],
+            },
+        ];
+
+        let event =
+            Event::from_message(Nl80211Cmd::CmdNotifyCqm, AttrHandle::Owned(handler)).unwrap();
+
+        assert_eq!(
+            event,
+            Event::NotifyCqm {
+                ifindex: Some(3),
+                mac: None,
+                cqm: CqmEvent {
+                    beacon_loss: true,
+                    ..Default::default()
+                },
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_authenticate_timeout() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 4,
+                nla_type: AttrTimedOut,
+                payload: vec![],
+            },
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![255, 255, 255, 255, 255, 255],
+            },
+        ];
+
+        let event =
+            Event::from_message(Nl80211Cmd::CmdAuthenticate, AttrHandle::Owned(handler)).unwrap();
+
+        assert_eq!(
+            event,
+            Event::Authenticate {
+                ifindex: Some(3),
+                bssid: Some(MacAddr::from([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])),
+                frame: None,
+                timed_out: true,
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_connect() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![255, 255, 255, 255, 255, 255],
+            },
+            Nlattr {
+                nla_len: 6,
+                nla_type: AttrStatusCode,
+                payload: vec![0, 0],
+            },
+            Nlattr {
+                nla_len: 13,
+                nla_type: AttrReqIe,
+                payload: vec![0, 7, 101, 100, 117, 114, 111, 97, 109],
+            },
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrRespIe,
+                payload: vec![1, 4, 2, 4, 11, 22],
+            },
+        ];
+
+        let event =
+            Event::from_message(Nl80211Cmd::CmdConnect, AttrHandle::Owned(handler)).unwrap();
+
+        assert_eq!(
+            event,
+            Event::Connect {
+                ifindex: Some(3),
+                bssid: Some(MacAddr::from([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])),
+                status: Some(0),
+                req_ie: Some(vec![0, 7, 101, 100, 117, 114, 111, 97, 109]),
+                resp_ie: Some(vec![1, 4, 2, 4, 11, 22]),
+            }
+        )
+    }
+}
@@ -1,5 +1,5 @@
+use crate::error::Error;
 use macaddr::MacAddr;
-use neli::err::NlError;
 use std::convert::TryInto;
 
 /// Parse a vec of bytes as a String
@@ -10,7 +10,7 @@ pub fn parse_string(input: &[u8]) -> String {
 }
 
 /// Parse a vec of bytes as a mac address
-pub fn parse_macaddr(input: &[u8]) -> Result<MacAddr, NlError> {
+pub fn parse_macaddr(input: &[u8]) -> Result<MacAddr, Error> {
     if input.len() == 6 {
         let array: [u8; 6] = input
             .try_into()
@@ -22,7 +22,7 @@ pub fn parse_macaddr(input: &[u8]) -> Result<MacAddr, NlError> {
             .expect("Slice with incorrect number of bytes");
         Ok(array.into())
     } else {
-        Err(NlError::Msg(format!(
+        Err(Error::Parse(format!(
             "Encountered a {}-byte MAC address",
             input.len()
         )))
@@ -0,0 +1,795 @@
+use crate::error::Error;
+use crate::helpers::parse_string;
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use std::convert::{TryFrom, TryInto};
+
+/// Element ids (IEEE 802.11-2016 9.4.2.1)
+const ELEMENT_SSID: u8 = 0;
+const ELEMENT_SUPPORTED_RATES: u8 = 1;
+const ELEMENT_DS_PARAMETER_SET: u8 = 3;
+const ELEMENT_COUNTRY: u8 = 7;
+const ELEMENT_HT_CAPABILITIES: u8 = 45;
+const ELEMENT_RSN: u8 = 48;
+const ELEMENT_EXTENDED_SUPPORTED_RATES: u8 = 50;
+const ELEMENT_MOBILITY_DOMAIN: u8 = 54;
+const ELEMENT_HT_OPERATION: u8 = 61;
+const ELEMENT_EXTENDED_CAPABILITIES: u8 = 127;
+const ELEMENT_VHT_CAPABILITIES: u8 = 191;
+const ELEMENT_VHT_OPERATION: u8 = 192;
+const ELEMENT_VENDOR_SPECIFIC: u8 = 221;
+const ELEMENT_EXTENSION: u8 = 255;
+
+/// Element id extensions of the HE elements (IEEE 802.11ax)
+const ELEMENT_EXT_HE_CAPABILITIES: u8 = 35;
+const ELEMENT_EXT_HE_OPERATION: u8 = 36;
+
+/// OUI of the suites defined by IEEE 802.11
+const OUI_IEEE80211: [u8; 3] = [0x00, 0x0F, 0xAC];
+
+/// OUI of Microsoft, used by WPA
+const OUI_MICROSOFT: [u8; 3] = [0x00, 0x50, 0xF2];
+
+/// Vendor specific type of the WPA element
+const VENDOR_TYPE_WPA: u8 = 1;
+
+/// An information element advertised in beacons and probe responses
+#[derive(Clone, Debug, PartialEq)]
+pub enum InformationElement {
+    /// Network name
+    Ssid(String),
+    /// Rates supported by the BSS
+    SupportedRates(Vec<SupportedRate>),
+    /// Rates supported by the BSS in addition to the ones of SupportedRates
+    ExtendedSupportedRates(Vec<SupportedRate>),
+    /// Current channel of the BSS
+    DsParameterSet(u8),
+    /// Country and regulatory constraints
+    Country(Country),
+    /// RSN (WPA2 and WPA3) security parameters
+    Rsn(Rsn),
+    /// WPA security parameters (vendor specific element of Microsoft)
+    Wpa(Rsn),
+    /// HT (802.11n) capabilities
+    HtCapabilities(HtCapabilities),
+    /// HT (802.11n) operation
+    HtOperation(HtOperation),
+    /// VHT (802.11ac) capabilities
+    VhtCapabilities(VhtCapabilities),
+    /// VHT (802.11ac) operation
+    VhtOperation(VhtOperation),
+    /// HE (802.11ax) capabilities
+    HeCapabilities(HeCapabilities),
+    /// HE (802.11ax) operation
+    HeOperation(HeOperation),
+    /// Extended capabilities bit field
+    ExtendedCapabilities(Vec<u8>),
+    /// Fast BSS transition (802.11r) mobility domain
+    MobilityDomain(MobilityDomain),
+    /// Vendor specific element
+    VendorSpecific { oui: [u8; 3], data: Vec<u8> },
+    /// Element with an unknown id or an invalid content
+    Unknown { id: u8, data: Vec<u8> },
+}
+
+/// A rate of the SupportedRates and ExtendedSupportedRates elements
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupportedRate {
+    /// Rate in units of 500 kb/s, or a BSS membership selector
+    pub rate: u8,
+    /// Whether stations must support this rate to join the BSS
+    pub basic: bool,
+}
+
+impl SupportedRate {
+    /// Rate in Mb/s
+    pub fn mbps(&self) -> f32 {
+        f32::from(self.rate) / 2.0
+    }
+}
+
+/// Content of the Country element
+#[derive(Clone, Debug, PartialEq)]
+pub struct Country {
+    /// ISO 3166-1 alpha-2 country code
+    pub code: String,
+    /// Environment: indoor (I), outdoor (O) or both (space)
+    pub environment: u8,
+    /// Channel ranges with their maximum transmit power
+    pub triplets: Vec<CountryTriplet>,
+}
+
+/// A channel range of the Country element
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountryTriplet {
+    /// First channel of the range
+    pub first_channel: u8,
+    /// Number of channels of the range
+    pub channels: u8,
+    /// Maximum transmit power in dBm
+    pub max_power: i8,
+}
+
+/// Security parameters of the RSN and WPA elements
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rsn {
+    pub version: u16,
+    /// Cipher used for broadcast and multicast frames
+    pub group_cipher: Option<CipherSuite>,
+    /// Ciphers available for unicast frames
+    pub pairwise_ciphers: Vec<CipherSuite>,
+    /// Authentication and key management suites
+    pub akm_suites: Vec<AkmSuite>,
+    /// RSN capabilities bit field (pre-authentication, management frame protection...)
+    pub capabilities: Option<u16>,
+}
+
+impl Rsn {
+    /// Whether management frame protection is required
+    pub fn mfp_required(&self) -> bool {
+        matches!(self.capabilities, Some(caps) if caps & (1 << 6) != 0)
+    }
+
+    /// Whether management frame protection is supported
+    pub fn mfp_capable(&self) -> bool {
+        matches!(self.capabilities, Some(caps) if caps & (1 << 7) != 0)
+    }
+
+    /// Encode the content of the RSN element
+    ///
+    /// The fields following a missing group cipher are left out, as the parser expects.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode(OUI_IEEE80211)
+    }
+
+    /// Encode the content of the WPA element, following its vendor type
+    ///
+    /// The known suites use the OUI of Microsoft, as in the element they were parsed from.
+    pub fn to_wpa_bytes(&self) -> Vec<u8> {
+        self.encode(OUI_MICROSOFT)
+    }
+
+    /// Encode the content of the RSN or WPA element, the known suites use the given OUI
+    fn encode(&self, oui: [u8; 3]) -> Vec<u8> {
+        let mut data = self.version.to_le_bytes().to_vec();
+
+        let group_cipher = match self.group_cipher {
+            Some(group_cipher) => group_cipher,
+            None => return data,
+        };
+        data.extend(&group_cipher.selector_with_oui(oui).to_be_bytes());
+
+        data.extend(&(self.pairwise_ciphers.len() as u16).to_le_bytes());
+        for cipher in &self.pairwise_ciphers {
+            data.extend(&cipher.selector_with_oui(oui).to_be_bytes());
+        }
+
+        data.extend(&(self.akm_suites.len() as u16).to_le_bytes());
+        for akm in &self.akm_suites {
+            data.extend(&akm.selector_with_oui(oui).to_be_bytes());
+        }
+
+        if let Some(capabilities) = self.capabilities {
+            data.extend(&capabilities.to_le_bytes());
+        }
+
+        data
+    }
+}
+
+/// A cipher suite, identified by its OUI and type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CipherSuite {
+    /// Use the group cipher suite
+    UseGroup,
+    /// WEP-40
+    Wep40,
+    /// TKIP
+    Tkip,
+    /// CCMP-128
+    Ccmp,
+    /// WEP-104
+    Wep104,
+    /// BIP-CMAC-128
+    BipCmac128,
+    /// Group addressed traffic not allowed
+    GroupNotAllowed,
+    /// GCMP-128
+    Gcmp,
+    /// GCMP-256
+    Gcmp256,
+    /// CCMP-256
+    Ccmp256,
+    /// BIP-GMAC-128
+    BipGmac128,
+    /// BIP-GMAC-256
+    BipGmac256,
+    /// BIP-CMAC-256
+    BipCmac256,
+    /// Any other suite selector (OUI followed by the suite type)
+    Other(u32),
+}
+
+impl CipherSuite {
+    /// Suite selector as used by nl80211 (OUI followed by the suite type)
+    ///
+    /// nl80211 identifies the WPA suites by the OUI of IEEE 802.11 as well.
+    pub fn selector(self) -> u32 {
+        self.selector_with_oui(OUI_IEEE80211)
+    }
+
+    /// Suite selector of a known suite with the given OUI, other selectors are kept as is
+    fn selector_with_oui(self, oui: [u8; 3]) -> u32 {
+        let suite_type = match self {
+            CipherSuite::UseGroup => 0,
+            CipherSuite::Wep40 => 1,
+            CipherSuite::Tkip => 2,
+            CipherSuite::Ccmp => 4,
+            CipherSuite::Wep104 => 5,
+            CipherSuite::BipCmac128 => 6,
+            CipherSuite::GroupNotAllowed => 7,
+            CipherSuite::Gcmp => 8,
+            CipherSuite::Gcmp256 => 9,
+            CipherSuite::Ccmp256 => 10,
+            CipherSuite::BipGmac128 => 11,
+            CipherSuite::BipGmac256 => 12,
+            CipherSuite::BipCmac256 => 13,
+            CipherSuite::Other(selector) => return selector,
+        };
+        suite(oui, suite_type)
+    }
+}
+
+impl From<u32> for CipherSuite {
+    fn from(selector: u32) -> Self {
+        let [a, b, c, suite_type] = selector.to_be_bytes();
+        // WPA uses the same suite types with the OUI of Microsoft
+        if [a, b, c] != OUI_IEEE80211 && [a, b, c] != OUI_MICROSOFT {
+            return CipherSuite::Other(selector);
+        }
+
+        match suite_type {
+            0 => CipherSuite::UseGroup,
+            1 => CipherSuite::Wep40,
+            2 => CipherSuite::Tkip,
+            4 => CipherSuite::Ccmp,
+            5 => CipherSuite::Wep104,
+            6 => CipherSuite::BipCmac128,
+            7 => CipherSuite::GroupNotAllowed,
+            8 => CipherSuite::Gcmp,
+            9 => CipherSuite::Gcmp256,
+            10 => CipherSuite::Ccmp256,
+            11 => CipherSuite::BipGmac128,
+            12 => CipherSuite::BipGmac256,
+            13 => CipherSuite::BipCmac256,
+            _ => CipherSuite::Other(selector),
+        }
+    }
+}
+
+/// An authentication and key management suite, identified by its OUI and type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AkmSuite {
+    Ieee8021x,
+    Psk,
+    FtIeee8021x,
+    FtPsk,
+    Ieee8021xSha256,
+    PskSha256,
+    Tdls,
+    Sae,
+    FtSae,
+    SuiteB,
+    SuiteB192,
+    FtIeee8021xSha384,
+    FilsSha256,
+    FilsSha384,
+    FtFilsSha256,
+    FtFilsSha384,
+    Owe,
+    /// Any other suite selector (OUI followed by the suite type)
+    Other(u32),
+}
+
+impl AkmSuite {
+    /// Suite selector as used by nl80211 (OUI followed by the suite type)
+    ///
+    /// nl80211 identifies the WPA suites by the OUI of IEEE 802.11 as well.
+    pub fn selector(self) -> u32 {
+        self.selector_with_oui(OUI_IEEE80211)
+    }
+
+    /// Suite selector of a known suite with the given OUI, other selectors are kept as is
+    fn selector_with_oui(self, oui: [u8; 3]) -> u32 {
+        let suite_type = match self {
+            AkmSuite::Ieee8021x => 1,
+            AkmSuite::Psk => 2,
+            AkmSuite::FtIeee8021x => 3,
+            AkmSuite::FtPsk => 4,
+            AkmSuite::Ieee8021xSha256 => 5,
+            AkmSuite::PskSha256 => 6,
+            AkmSuite::Tdls => 7,
+            AkmSuite::Sae => 8,
+            AkmSuite::FtSae => 9,
+            AkmSuite::SuiteB => 11,
+            AkmSuite::SuiteB192 => 12,
+            AkmSuite::FtIeee8021xSha384 => 13,
+            AkmSuite::FilsSha256 => 14,
+            AkmSuite::FilsSha384 => 15,
+            AkmSuite::FtFilsSha256 => 16,
+            AkmSuite::FtFilsSha384 => 17,
+            AkmSuite::Owe => 18,
+            AkmSuite::Other(selector) => return selector,
+        };
+        suite(oui, suite_type)
+    }
+}
+
+impl From<u32> for AkmSuite {
+    fn from(selector: u32) -> Self {
+        let [a, b, c, suite_type] = selector.to_be_bytes();
+        // WPA uses the same suite types with the OUI of Microsoft
+        if [a, b, c] != OUI_IEEE80211 && [a, b, c] != OUI_MICROSOFT {
+            return AkmSuite::Other(selector);
+        }
+
+        match suite_type {
+            1 => AkmSuite::Ieee8021x,
+            2 => AkmSuite::Psk,
+            3 => AkmSuite::FtIeee8021x,
+            4 => AkmSuite::FtPsk,
+            5 => AkmSuite::Ieee8021xSha256,
+            6 => AkmSuite::PskSha256,
+            7 => AkmSuite::Tdls,
+            8 => AkmSuite::Sae,
+            9 => AkmSuite::FtSae,
+            11 => AkmSuite::SuiteB,
+            12 => AkmSuite::SuiteB192,
+            13 => AkmSuite::FtIeee8021xSha384,
+            14 => AkmSuite::FilsSha256,
+            15 => AkmSuite::FilsSha384,
+            16 => AkmSuite::FtFilsSha256,
+            17 => AkmSuite::FtFilsSha384,
+            18 => AkmSuite::Owe,
+            _ => AkmSuite::Other(selector),
+        }
+    }
+}
+
+/// Content of the HT Capabilities element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtCapabilities {
+    /// HT capability information bit field
+    pub capabilities: u16,
+    /// A-MPDU parameters
+    pub ampdu_parameters: u8,
+    /// Supported MCS set
+    pub mcs_set: [u8; 16],
+    pub extended_capabilities: u16,
+    pub tx_beamforming_capabilities: u32,
+    pub asel_capabilities: u8,
+}
+
+impl HtCapabilities {
+    /// Whether 40 MHz channels are supported
+    pub fn supports_40mhz(&self) -> bool {
+        self.capabilities & (1 << 1) != 0
+    }
+
+    /// Number of spatial streams supported for reception
+    pub fn rx_spatial_streams(&self) -> usize {
+        self.mcs_set[..4].iter().filter(|mcs| **mcs != 0).count()
+    }
+}
+
+/// Content of the HT Operation element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtOperation {
+    pub primary_channel: u8,
+    /// Position of the secondary channel: none (0), above (1) or below (3) the primary channel
+    pub secondary_channel_offset: u8,
+    /// Whether the BSS may use a 40 MHz channel
+    pub any_channel_width: bool,
+    /// HT operation information bit field
+    pub information: [u8; 5],
+    /// MCS supported by every station of the BSS
+    pub basic_mcs_set: [u8; 16],
+}
+
+/// Content of the VHT Capabilities element
+#[derive(Clone, Debug, PartialEq)]
+pub struct VhtCapabilities {
+    /// VHT capability information bit field
+    pub capabilities: u32,
+    /// Maximum MCS supported for reception for each number of spatial streams
+    pub rx_mcs_map: u16,
+    /// Highest supported reception rate in Mb/s
+    pub rx_highest_rate: u16,
+    /// Maximum MCS supported for transmission for each number of spatial streams
+    pub tx_mcs_map: u16,
+    /// Highest supported transmission rate in Mb/s
+    pub tx_highest_rate: u16,
+}
+
+impl VhtCapabilities {
+    /// Whether 160 MHz or 80+80 MHz channels are supported
+    pub fn supports_160mhz(&self) -> bool {
+        (self.capabilities >> 2) & 0b11 != 0
+    }
+
+    /// Number of spatial streams supported for reception
+    pub fn rx_spatial_streams(&self) -> usize {
+        (0..8)
+            .filter(|nss| (self.rx_mcs_map >> (nss * 2)) & 0b11 != 0b11)
+            .count()
+    }
+}
+
+/// Content of the VHT Operation element
+#[derive(Clone, Debug, PartialEq)]
+pub struct VhtOperation {
+    /// Channel width: 20 or 40 MHz (0), 80, 160 or 80+80 MHz (1)
+    pub channel_width: u8,
+    /// Channel center frequency segment 0 (channel number)
+    pub center_segment0: u8,
+    /// Channel center frequency segment 1 (channel number)
+    pub center_segment1: u8,
+    /// MCS supported by every station of the BSS for each number of spatial streams
+    pub basic_mcs_map: u16,
+}
+
+/// Content of the HE Capabilities element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeCapabilities {
+    /// HE MAC capabilities bit field
+    pub mac_capabilities: [u8; 6],
+    /// HE PHY capabilities bit field
+    pub phy_capabilities: [u8; 11],
+    /// Supported HE-MCS and NSS set, followed by the optional PPE thresholds
+    pub mcs_nss: Vec<u8>,
+}
+
+/// Content of the HE Operation element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeOperation {
+    /// HE operation parameters bit field (3 bytes)
+    pub parameters: u32,
+    /// BSS color information
+    pub bss_color: u8,
+    /// HE-MCS supported by every station of the BSS for each number of spatial streams
+    pub basic_mcs_nss: u16,
+    /// Optional fields (VHT operation, co-hosted BSSID, 6 GHz operation)
+    pub optional: Vec<u8>,
+}
+
+/// Content of the Mobility Domain element
+#[derive(Clone, Debug, PartialEq)]
+pub struct MobilityDomain {
+    /// Mobility domain identifier
+    pub mdid: u16,
+    /// Fast BSS transition capability and policy
+    pub ft_capabilities: u8,
+}
+
+/// Parse a blob of information elements
+///
+/// Elements that can't be decoded are kept raw as `InformationElement::Unknown`, a truncated
+/// element ends the parsing.
+pub fn parse_information_elements(input: &[u8]) -> Vec<InformationElement> {
+    let mut elements = Vec::new();
+    let mut input = input;
+
+    while input.len() >= 2 {
+        let id = input[0];
+        let len = input[1] as usize;
+        let data = match input.get(2..2 + len) {
+            Some(data) => data,
+            None => break,
+        };
+
+        elements.push(
+            parse_element(id, data).unwrap_or_else(|_| InformationElement::Unknown {
+                id,
+                data: data.to_vec(),
+            }),
+        );
+
+        input = &input[2 + len..];
+    }
+
+    elements
+}
+
+/// Decode an element from its id and content
+fn parse_element(id: u8, data: &[u8]) -> Result<InformationElement, Error> {
+    let mut payload = data;
+
+    let element = match id {
+        ELEMENT_SSID => InformationElement::Ssid(parse_string(data)),
+        ELEMENT_SUPPORTED_RATES => InformationElement::SupportedRates(parse_rates(data)),
+        ELEMENT_EXTENDED_SUPPORTED_RATES => {
+            InformationElement::ExtendedSupportedRates(parse_rates(data))
+        }
+        ELEMENT_DS_PARAMETER_SET => InformationElement::DsParameterSet(payload.read_u8()?),
+        ELEMENT_COUNTRY => {
+            let code = parse_string(slice(data, 0, 2)?);
+            let environment = *slice(data, 2, 1)?.first().unwrap_or(&0);
+            let triplets = data[3..]
+                .chunks_exact(3)
+                .map(|triplet| CountryTriplet {
+                    first_channel: triplet[0],
+                    channels: triplet[1],
+                    max_power: triplet[2] as i8,
+                })
+                .collect();
+
+            InformationElement::Country(Country {
+                code,
+                environment,
+                triplets,
+            })
+        }
+        ELEMENT_RSN => InformationElement::Rsn(parse_rsn(data)?),
+        ELEMENT_HT_CAPABILITIES => InformationElement::HtCapabilities(HtCapabilities {
+            capabilities: payload.read_u16::<LittleEndian>()?,
+            ampdu_parameters: payload.read_u8()?,
+            mcs_set: read_array(&mut payload)?,
+            extended_capabilities: payload.read_u16::<LittleEndian>()?,
+            tx_beamforming_capabilities: payload.read_u32::<LittleEndian>()?,
+            asel_capabilities: payload.read_u8()?,
+        }),
+        ELEMENT_HT_OPERATION => {
+            let primary_channel = payload.read_u8()?;
+            let information: [u8; 5] = read_array(&mut payload)?;
+
+            InformationElement::HtOperation(HtOperation {
+                primary_channel,
+                secondary_channel_offset: information[0] & 0b11,
+                any_channel_width: information[0] & (1 << 2) != 0,
+                information,
+                basic_mcs_set: read_array(&mut payload)?,
+            })
+        }
+        ELEMENT_VHT_CAPABILITIES => InformationElement::VhtCapabilities(VhtCapabilities {
+            capabilities: payload.read_u32::<LittleEndian>()?,
+            rx_mcs_map: payload.read_u16::<LittleEndian>()?,
+            rx_highest_rate: payload.read_u16::<LittleEndian>()? & 0x1FFF,
+            tx_mcs_map: payload.read_u16::<LittleEndian>()?,
+            tx_highest_rate: payload.read_u16::<LittleEndian>()? & 0x1FFF,
+        }),
+        ELEMENT_VHT_OPERATION => InformationElement::VhtOperation(VhtOperation {
+            channel_width: payload.read_u8()?,
+            center_segment0: payload.read_u8()?,
+            center_segment1: payload.read_u8()?,
+            basic_mcs_map: payload.read_u16::<LittleEndian>()?,
+        }),
+        ELEMENT_EXTENDED_CAPABILITIES => InformationElement::ExtendedCapabilities(data.to_vec()),
+        ELEMENT_MOBILITY_DOMAIN => InformationElement::MobilityDomain(MobilityDomain {
+            mdid: payload.read_u16::<LittleEndian>()?,
+            ft_capabilities: payload.read_u8()?,
+        }),
+        ELEMENT_VENDOR_SPECIFIC => {
+            let oui: [u8; 3] = read_array(&mut payload)?;
+            if oui == OUI_MICROSOFT && payload.first() == Some(&VENDOR_TYPE_WPA) {
+                InformationElement::Wpa(parse_rsn(&payload[1..])?)
+            } else {
+                InformationElement::VendorSpecific {
+                    oui,
+                    data: payload.to_vec(),
+                }
+            }
+        }
+        ELEMENT_EXTENSION => match payload.read_u8()? {
+            ELEMENT_EXT_HE_CAPABILITIES => InformationElement::HeCapabilities(HeCapabilities {
+                mac_capabilities: read_array(&mut payload)?,
+                phy_capabilities: read_array(&mut payload)?,
+                mcs_nss: payload.to_vec(),
+            }),
+            ELEMENT_EXT_HE_OPERATION => InformationElement::HeOperation(HeOperation {
+                parameters: payload.read_u24::<LittleEndian>()?,
+                bss_color: payload.read_u8()?,
+                basic_mcs_nss: payload.read_u16::<LittleEndian>()?,
+                optional: payload.to_vec(),
+            }),
+            _ => InformationElement::Unknown {
+                id,
+                data: data.to_vec(),
+            },
+        },
+        _ => InformationElement::Unknown {
+            id,
+            data: data.to_vec(),
+        },
+    };
+
+    Ok(element)
+}
+
+/// Parse the rates of the SupportedRates and ExtendedSupportedRates elements
+fn parse_rates(data: &[u8]) -> Vec<SupportedRate> {
+    data.iter()
+        .map(|rate| SupportedRate {
+            rate: rate & 0x7F,
+            basic: rate & 0x80 != 0,
+        })
+        .collect()
+}
+
+/// Parse the content of the RSN element, WPA uses the same layout
+///
+/// Every field following the version is optional.
+fn parse_rsn(data: &[u8]) -> Result<Rsn, Error> {
+    let mut payload = data;
+
+    let mut rsn = Rsn {
+        version: payload.read_u16::<LittleEndian>()?,
+        group_cipher: None,
+        pairwise_ciphers: Vec::new(),
+        akm_suites: Vec::new(),
+        capabilities: None,
+    };
+
+    if payload.is_empty() {
+        return Ok(rsn);
+    }
+    rsn.group_cipher = Some(CipherSuite::from(payload.read_u32::<BigEndian>()?));
+
+    if payload.is_empty() {
+        return Ok(rsn);
+    }
+    for _ in 0..payload.read_u16::<LittleEndian>()? {
+        rsn.pairwise_ciphers
+            .push(CipherSuite::from(payload.read_u32::<BigEndian>()?));
+    }
+
+    if payload.is_empty() {
+        return Ok(rsn);
+    }
+    for _ in 0..payload.read_u16::<LittleEndian>()? {
+        rsn.akm_suites
+            .push(AkmSuite::from(payload.read_u32::<BigEndian>()?));
+    }
+
+    if payload.len() >= 2 {
+        rsn.capabilities = Some(payload.read_u16::<LittleEndian>()?);
+    }
+
+    Ok(rsn)
+}
+
+/// Build a suite selector from an OUI and a suite type
+fn suite(oui: [u8; 3], suite_type: u8) -> u32 {
+    u32::from_be_bytes([oui[0], oui[1], oui[2], suite_type])
+}
+
+/// Get a sub slice of an element, failing if the element is too short
+fn slice(data: &[u8], start: usize, len: usize) -> Result<&[u8], Error> {
+    data.get(start..start + len)
+        .ok_or_else(|| Error::Parse("Information element is too short".into()))
+}
+
+/// Read a fixed size field of an element
+fn read_array<T>(payload: &mut &[u8]) -> Result<T, Error>
+where
+    for<'a> T: TryFrom<&'a [u8]>,
+{
+    let len = std::mem::size_of::<T>();
+    let field = slice(payload, 0, len)?
+        .try_into()
+        .map_err(|_| Error::Parse("Information element is too short".into()))?;
+    *payload = &payload[len..];
+    Ok(field)
+}
+
+#[cfg(test)]
+mod test_ie {
+    use super::*;
+
+    #[test]
+    fn test_parse_basic_elements() {
+        let input = vec![
+            0, 8, 83, 70, 82, 45, 49, 99, 50, 56, 1, 8, 130, 132, 139, 150, 36, 48, 72, 108, 3, 1,
+            1, 7, 6, 68, 69, 32, 1, 13, 20, 54, 3, 52, 18, 1, 42, 1, 0,
+        ];
+
+        let elements = parse_information_elements(&input);
+
+        assert_eq!(elements.len(), 6);
+        assert_eq!(elements[0], InformationElement::Ssid("SFR-1c28".into()));
+        assert_eq!(
+            elements[1],
+            InformationElement::SupportedRates(
+                [(2, true), (4, true), (11, true), (22, true), (36, false)]
+                    .iter()
+                    .chain([(48, false), (72, false), (108, false)].iter())
+                    .map(|(rate, basic)| SupportedRate {
+                        rate: *rate,
+                        basic: *basic
+                    })
+                    .collect()
+            )
+        );
+        assert_eq!(elements[2], InformationElement::DsParameterSet(1));
+        assert_eq!(
+            elements[3],
+            InformationElement::Country(Country {
+                code: "DE".into(),
+                environment: b' ',
+                triplets: vec![CountryTriplet {
+                    first_channel: 1,
+                    channels: 13,
+                    max_power: 20,
+                }],
+            })
+        );
+        assert_eq!(
+            elements[4],
+            InformationElement::MobilityDomain(MobilityDomain {
+                mdid: 0x1234,
+                ft_capabilities: 1,
+            })
+        );
+        assert_eq!(
+            elements[5],
+            InformationElement::Unknown {
+                id: 42,
+                data: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rsn() {
+        let input = vec![
+            48, 24, 1, 0, 0, 15, 172, 4, 1, 0, 0, 15, 172, 4, 2, 0, 0, 15, 172, 2, 0, 15, 172, 8,
+            192, 0, 221, 22, 0, 80, 242, 1, 1, 0, 0, 80, 242, 2, 1, 0, 0, 80, 242, 2, 1, 0, 0, 80,
+            242, 2,
+        ];
+
+        let elements = parse_information_elements(&input);
+
+        assert_eq!(
+            elements,
+            vec![
+                InformationElement::Rsn(Rsn {
+                    version: 1,
+                    group_cipher: Some(CipherSuite::Ccmp),
+                    pairwise_ciphers: vec![CipherSuite::Ccmp],
+                    akm_suites: vec![AkmSuite::Psk, AkmSuite::Sae],
+                    capabilities: Some(0xC0),
+                }),
+                InformationElement::Wpa(Rsn {
+                    version: 1,
+                    group_cipher: Some(CipherSuite::Tkip),
+                    pairwise_ciphers: vec![CipherSuite::Tkip],
+                    akm_suites: vec![AkmSuite::Psk],
+                    capabilities: None,
+                }),
+            ]
+        );
+        assert_eq!(CipherSuite::Ccmp.selector(), 0x000F_AC04);
+        if let InformationElement::Rsn(rsn) = &elements[0] {
+            assert_eq!(rsn.to_bytes(), input[2..26].to_vec());
+        }
+        // WPA suites keep the OUI of Microsoft in the WPA element
+        if let InformationElement::Wpa(wpa) = &elements[1] {
+            assert_eq!(wpa.to_wpa_bytes(), input[32..].to_vec());
+        }
+        assert_eq!(CipherSuite::Tkip.selector(), 0x000F_AC02);
+    }
+
+    #[test]
+    fn test_parse_truncated_elements() {
+        // A too short HT Capabilities element followed by a truncated SSID
+        let input = vec![45, 2, 1, 2, 0, 8, 83, 70];
+
+        let elements = parse_information_elements(&input);
+
+        assert_eq!(
+            elements,
+            vec![InformationElement::Unknown {
+                id: 45,
+                data: vec![1, 2],
+            }]
+        );
+    }
+}
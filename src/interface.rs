@@ -1,13 +1,17 @@
 use crate::attr::*;
 use crate::bss::Bss;
+use crate::channel::{frequency_to_channel, ChannelDef};
+use crate::connect::{ConnectParams, ConnectResult};
+use crate::error::Error;
 use crate::helpers::{parse_macaddr, parse_string};
-use crate::nl80211traits::FromNlAttributeHandle;
+use crate::nl80211traits::{FromNlAttributeHandle, ToNlAttributes};
+use crate::scan::ScanRequest;
 use crate::socket::Socket;
 use crate::station::Station;
+use crate::txpower::TxPower;
 use byteorder::{LittleEndian, ReadBytesExt};
 use macaddr::MacAddr;
-use neli::err::NlError;
-use neli::nlattr::AttrHandle;
+use neli::nlattr::{AttrHandle, Nlattr};
 use std::fmt;
 
 /// A struct representing a wifi interface
@@ -25,33 +29,185 @@ pub struct Interface {
     pub frequency: Option<u32>,
     /// Interface channel
     pub channel: Option<u32>,
+    /// Definition of the operating channel (width and center frequencies)
+    pub channel_def: Option<ChannelDef>,
     /// Interface transmit power level in signed mBm units.
-    pub power: Option<u32>,
+    ///
+    /// nl80211 only reports the level currently in use, not the `TxPower` setting which led to
+    /// it, the setting can't be read back.
+    pub power: Option<i32>,
     /// index of wiphy to operate on, cf. /sys/class/ieee80211/<phyname>/index
     pub phy: Option<u32>,
     /// Wireless device identifier, used for pseudo-devices that don't have a netdev
     pub device: Option<u64>,
 }
 
+/// Optional settings of an interface, used by the nl80211 commands CmdNewInterface and
+/// CmdSetInterface
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterfaceOptions {
+    /// MAC address of the interface, the wiphy address is used when not set. It can only be set
+    /// when the interface is created, changing the settings of an interface fails when it is set
+    pub mac: Option<MacAddr>,
+    /// Enable or disable 4-address mode (WDS frames)
+    pub four_address: Option<bool>,
+    /// Monitor flags, for monitor interfaces only
+    pub monitor_flags: Vec<Nl80211MntrFlags>,
+    /// Mesh ID, for mesh point interfaces only
+    pub mesh_id: Option<String>,
+}
+
+impl ToNlAttributes for InterfaceOptions {
+    /// Build the attributes of the nl80211 commands CmdNewInterface and CmdSetInterface
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = Vec::new();
+
+        if let Some(mac) = &self.mac {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMac,
+                mac.as_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(four_address) = self.four_address {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::Attr4addr,
+                vec![four_address as u8],
+            )?);
+        }
+
+        if !self.monitor_flags.is_empty() {
+            let mut flags = Nlattr::new(None, Nl80211Attr::AttrMntrFlags, Vec::<u8>::new())?;
+            for flag in &self.monitor_flags {
+                flags.add_nested_attribute(&Nlattr::new(None, flag.clone(), Vec::<u8>::new())?)?;
+            }
+            attrs.push(flags);
+        }
+
+        if let Some(mesh_id) = &self.mesh_id {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMeshId,
+                mesh_id.as_bytes().to_vec(),
+            )?);
+        }
+
+        Ok(attrs)
+    }
+}
+
 impl Interface {
     /// Get station info for this interface
-    pub fn get_station_info(&self) -> Result<Station, neli::err::NlError> {
+    pub fn get_station_info(&self) -> Result<Station, Error> {
         if let Some(index) = self.index {
             Socket::connect()?.get_station_info(index)
         } else {
-            Err(neli::err::NlError::new(
-                "Can't get Station from incomplete interface",
+            Err(Error::Invalid(
+                "Can't get Station from incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Get every station known by this interface
+    pub fn get_stations(&self) -> Result<Vec<Station>, Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.get_stations(index)
+        } else {
+            Err(Error::Invalid(
+                "Can't get Stations from incomplete interface".into(),
             ))
         }
     }
 
     /// Get bss info for this interface
-    pub fn get_bss_info(&self) -> Result<Bss, neli::err::NlError> {
+    pub fn get_bss_info(&self) -> Result<Bss, Error> {
         if let Some(index) = self.index {
             Socket::connect()?.get_bss_info(index)
         } else {
-            Err(neli::err::NlError::new(
-                "Can't get Bss from incomplete interface",
+            Err(Error::Invalid(
+                "Can't get Bss from incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Get every BSS found by the last scans of this interface
+    pub fn get_scan_results(&self) -> Result<Vec<Bss>, Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.get_scan_results(index)
+        } else {
+            Err(Error::Invalid(
+                "Can't get scan results from incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Scan for networks with this interface and wait for the results
+    pub fn scan(&self, request: &ScanRequest) -> Result<Vec<Bss>, Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.scan(index, request)
+        } else {
+            Err(Error::Invalid(
+                "Can't scan from incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Connect this interface to a network and wait for the result
+    pub fn connect_network(&self, params: &ConnectParams) -> Result<ConnectResult, Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.connect_network(index, params)
+        } else {
+            Err(Error::Invalid(
+                "Can't connect from incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Disconnect this interface from its network
+    pub fn disconnect(&self, reason: u16) -> Result<(), Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.disconnect(index, reason)
+        } else {
+            Err(Error::Invalid(
+                "Can't disconnect incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Get the power save state of this interface
+    pub fn get_power_save(&self) -> Result<Nl80211PsState, Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.get_power_save(index)
+        } else {
+            Err(Error::Invalid(
+                "Can't get power save state of incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Enable or disable power save on this interface
+    pub fn set_power_save(&self, state: Nl80211PsState) -> Result<(), Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.set_power_save(index, state)
+        } else {
+            Err(Error::Invalid(
+                "Can't set power save state of incomplete interface".into(),
+            ))
+        }
+    }
+
+    /// Set the transmit power of this interface
+    ///
+    /// The power level is checked against the maximum power of the current channel, or of the
+    /// channels of the wiphy when the interface isn't operating, when the wiphy reports it.
+    pub fn set_tx_power(&self, power: &TxPower) -> Result<(), Error> {
+        if let Some(index) = self.index {
+            Socket::connect()?.set_tx_power(index, power)
+        } else {
+            Err(Error::Invalid(
+                "Can't set transmit power of incomplete interface".into(),
             ))
         }
     }
@@ -59,10 +215,14 @@ impl Interface {
 
 impl FromNlAttributeHandle for Interface {
     /// Parse netlink messages returned by the nl80211 command CmdGetInterface
-    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Interface, NlError> {
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Interface, Error> {
         let mut interface = Interface {
             ..Default::default()
         };
+        let mut width = None;
+        let mut center_freq1 = None;
+        let mut center_freq2 = None;
+        let mut channel_type = None;
         for attr in handle.iter() {
             let mut payload = &attr.payload[..];
             match attr.nla_type {
@@ -82,10 +242,23 @@ impl FromNlAttributeHandle for Interface {
                     interface.frequency = Some(payload.read_u32::<LittleEndian>()?)
                 }
                 Nl80211Attr::AttrChannelWidth => {
-                    interface.channel = Some(payload.read_u32::<LittleEndian>()?)
+                    width = Some(Nl80211ChanWidth::from(
+                        payload.read_u32::<LittleEndian>()? as u16
+                    ))
+                }
+                Nl80211Attr::AttrCenterFreq1 => {
+                    center_freq1 = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211Attr::AttrCenterFreq2 => {
+                    center_freq2 = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211Attr::AttrWiphyChannelType => {
+                    channel_type = Some(Nl80211ChannelType::from(
+                        payload.read_u32::<LittleEndian>()? as u16,
+                    ))
                 }
                 Nl80211Attr::AttrWiphyTxPowerLevel => {
-                    interface.power = Some(payload.read_u32::<LittleEndian>()?)
+                    interface.power = Some(payload.read_i32::<LittleEndian>()?)
                 }
                 Nl80211Attr::AttrWiphy => interface.phy = Some(payload.read_u32::<LittleEndian>()?),
                 Nl80211Attr::AttrWdev => {
@@ -94,10 +267,36 @@ impl FromNlAttributeHandle for Interface {
                 _ => (),
             }
         }
+
+        if let Some(frequency) = interface.frequency {
+            interface.channel = frequency_to_channel(frequency);
+            interface.channel_def = Some(ChannelDef {
+                frequency,
+                width,
+                center_freq1,
+                center_freq2,
+                channel_type,
+            });
+        }
+
         Ok(interface)
     }
 }
 
+impl FromNlAttributeHandle for Nl80211PsState {
+    /// Parse netlink messages returned by the nl80211 command CmdGetPowerSave
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Nl80211PsState, Error> {
+        for attr in handle.iter() {
+            if attr.nla_type == Nl80211Attr::AttrPsState {
+                let state = (&attr.payload[..]).read_u32::<LittleEndian>()?;
+                return Ok(Nl80211PsState::from(state as u16));
+            }
+        }
+
+        Err(Error::Parse("Power save state is missing".into()))
+    }
+}
+
 impl fmt::Display for Interface {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut result = Vec::new();
@@ -114,13 +313,17 @@ impl fmt::Display for Interface {
             result.push(format!("interface : {}", name))
         };
 
-        if let Some(frequency) = self.frequency {
-            result.push(format!("frequency : {} Ghz", frequency as f64 / 1000.00))
-        };
+        if let Some(channel_def) = &self.channel_def {
+            result.push(channel_def.to_string())
+        } else {
+            if let Some(frequency) = self.frequency {
+                result.push(format!("frequency : {} Ghz", frequency as f64 / 1000.00))
+            };
 
-        if let Some(chanel) = &self.channel {
-            result.push(format!("channel : {}", chanel))
-        };
+            if let Some(chanel) = &self.channel {
+                result.push(format!("channel : {}", chanel))
+            };
+        }
 
         if let Some(power) = &self.power {
             result.push(format!("power : {} dBm", power / 100))
@@ -142,7 +345,6 @@ impl fmt::Display for Interface {
 mod test_interface {
     use super::*;
     use crate::attr::Nl80211Attr::*;
-    use neli::nlattr::Nlattr;
 
     #[test]
     fn test_pretty_format() {
@@ -153,6 +355,7 @@ mod test_interface {
             name: Some("wlp5s0".into()),
             frequency: Some(2412),
             channel: Some(1),
+            channel_def: None,
             power: Some(1700),
             phy: Some(0),
             device: Some(1),
@@ -257,6 +460,13 @@ mod test_interface {
             name: Some("wlp5s0".into()),
             frequency: Some(2412),
             channel: Some(1),
+            channel_def: Some(ChannelDef {
+                frequency: 2412,
+                width: Some(Nl80211ChanWidth::ChanWidth20),
+                center_freq1: Some(2412),
+                center_freq2: None,
+                channel_type: Some(Nl80211ChannelType::ChanHt20),
+            }),
             power: Some(1700),
             phy: Some(0),
             device: Some(1),
@@ -264,4 +474,89 @@ mod test_interface {
 
         assert_eq!(interface, expected_interface)
     }
+
+    #[test]
+    fn test_pretty_format_channel_def() {
+        let interface = Interface {
+            name: Some("wlp5s0".into()),
+            frequency: Some(5180),
+            channel: Some(36),
+            channel_def: Some(ChannelDef {
+                frequency: 5180,
+                width: Some(Nl80211ChanWidth::ChanWidth80),
+                center_freq1: Some(5210),
+                center_freq2: None,
+                channel_type: None,
+            }),
+            ..Default::default()
+        };
+
+        let expected_output = r#"interface : wlp5s0
+        channel 36 (5180 MHz), width: 80 MHz, center1: 5210 MHz"#;
+
+        assert_eq!(
+            format!("{}", interface),
+            expected_output.replace("\n        ", "\n")
+        )
+    }
+
+    #[test]
+    fn test_parse_power_save() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrPsState,
+                payload: vec![1, 0, 0, 0],
+            },
+        ];
+
+        let state = Nl80211PsState::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        assert_eq!(state, Nl80211PsState::PsEnabled)
+    }
+
+    #[test]
+    fn test_options_to_attrs() {
+        let options = InterfaceOptions {
+            mac: Some(MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00])),
+            four_address: Some(true),
+            monitor_flags: vec![
+                Nl80211MntrFlags::MntrFlagControl,
+                Nl80211MntrFlags::MntrFlagOtherBss,
+            ],
+            mesh_id: Some("mesh".into()),
+        };
+
+        let attrs = options.to_attrs().unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![2, 0, 0, 0, 1, 0],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: Attr4addr,
+                payload: vec![1],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrMntrFlags,
+                payload: vec![4, 0, 3, 0, 4, 0, 4, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrMeshId,
+                payload: vec![109, 101, 115, 104],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
 }
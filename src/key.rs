@@ -0,0 +1,290 @@
+use crate::attr::{Nl80211Attr, Nl80211KeyAttributes, Nl80211KeyDefaultTypes, Nl80211KeyType};
+use crate::error::Error;
+use crate::helpers::parse_macaddr;
+use crate::ie::CipherSuite;
+use crate::nl80211traits::{FromNlAttributeHandle, ToNlAttributes};
+use byteorder::{LittleEndian, ReadBytesExt};
+use macaddr::MacAddr;
+use neli::nlattr::{AttrHandle, Nlattr};
+
+/// An encryption key installed with the nl80211 command CmdNewKey
+#[derive(Clone, Debug, PartialEq)]
+pub struct Key {
+    /// Key index (0-3 for pairwise and group keys, 4-5 for management group keys)
+    pub index: u8,
+    /// Cipher suite of the key
+    pub cipher: CipherSuite,
+    /// Key material
+    pub data: Vec<u8>,
+    /// Receive sequence counter, for group keys
+    pub seq: Option<Vec<u8>>,
+    /// Key type, deduced by the kernel from the peer address when not set
+    pub key_type: Option<Nl80211KeyType>,
+    /// MAC address of the peer, for pairwise keys
+    pub mac: Option<MacAddr>,
+}
+
+impl Key {
+    /// Create a pairwise key shared with a peer
+    pub fn pairwise(mac: MacAddr, cipher: CipherSuite, data: Vec<u8>) -> Key {
+        Key {
+            index: 0,
+            cipher,
+            data,
+            seq: None,
+            key_type: Some(Nl80211KeyType::KeytypePairwise),
+            mac: Some(mac),
+        }
+    }
+
+    /// Create a group key
+    pub fn group(index: u8, cipher: CipherSuite, data: Vec<u8>) -> Key {
+        Key {
+            index,
+            cipher,
+            data,
+            seq: None,
+            key_type: Some(Nl80211KeyType::KeytypeGroup),
+            mac: None,
+        }
+    }
+}
+
+impl ToNlAttributes for Key {
+    /// Build the attributes of the nl80211 command CmdNewKey
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = Vec::new();
+
+        if let Some(mac) = &self.mac {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMac,
+                mac.as_bytes().to_vec(),
+            )?);
+        }
+
+        let mut key = Nlattr::new(None, Nl80211Attr::AttrKey, Vec::<u8>::new())?;
+        key.add_nested_attribute(&Nlattr::new(
+            None,
+            Nl80211KeyAttributes::KeyData,
+            self.data.clone(),
+        )?)?;
+        key.add_nested_attribute(&Nlattr::new(
+            None,
+            Nl80211KeyAttributes::KeyIdx,
+            vec![self.index],
+        )?)?;
+        key.add_nested_attribute(&Nlattr::new(
+            None,
+            Nl80211KeyAttributes::KeyCipher,
+            self.cipher.selector().to_le_bytes().to_vec(),
+        )?)?;
+        if let Some(seq) = &self.seq {
+            key.add_nested_attribute(&Nlattr::new(
+                None,
+                Nl80211KeyAttributes::KeySeq,
+                seq.clone(),
+            )?)?;
+        }
+        if let Some(key_type) = &self.key_type {
+            key.add_nested_attribute(&Nlattr::new(
+                None,
+                Nl80211KeyAttributes::KeyType,
+                u32::from(u16::from(key_type)).to_le_bytes().to_vec(),
+            )?)?;
+        }
+        attrs.push(key);
+
+        Ok(attrs)
+    }
+}
+
+/// Default key selection made with the nl80211 command CmdSetKey
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DefaultKey {
+    /// Index of the key to use by default
+    pub index: u8,
+    /// Select the default management frame key instead of the default data key
+    pub management: bool,
+    /// Traffic (unicast and/or multicast) the default key is used for, every traffic when empty
+    pub types: Vec<Nl80211KeyDefaultTypes>,
+}
+
+impl ToNlAttributes for DefaultKey {
+    /// Build the attributes of the nl80211 command CmdSetKey
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut key = Nlattr::new(None, Nl80211Attr::AttrKey, Vec::<u8>::new())?;
+        key.add_nested_attribute(&Nlattr::new(
+            None,
+            Nl80211KeyAttributes::KeyIdx,
+            vec![self.index],
+        )?)?;
+
+        let default = match self.management {
+            true => Nl80211KeyAttributes::KeyDefaultMgmt,
+            false => Nl80211KeyAttributes::KeyDefault,
+        };
+        key.add_nested_attribute(&Nlattr::new(None, default, Vec::<u8>::new())?)?;
+
+        if !self.types.is_empty() {
+            let mut types = Nlattr::new(
+                None,
+                Nl80211KeyAttributes::KeyDefaultTypes,
+                Vec::<u8>::new(),
+            )?;
+            for key_type in &self.types {
+                types.add_nested_attribute(&Nlattr::new(
+                    None,
+                    key_type.clone(),
+                    Vec::<u8>::new(),
+                )?)?;
+            }
+            key.add_nested_attribute(&types)?;
+        }
+
+        Ok(vec![key])
+    }
+}
+
+/// Information about an installed key, returned by the nl80211 command CmdGetKey
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyInfo {
+    /// Key index
+    pub index: Option<u8>,
+    /// MAC address of the peer, for pairwise keys
+    pub mac: Option<MacAddr>,
+    /// Cipher suite of the key
+    pub cipher: Option<CipherSuite>,
+    /// Current sequence counter of the key
+    pub seq: Option<Vec<u8>>,
+}
+
+impl FromNlAttributeHandle for KeyInfo {
+    /// Parse netlink messages returned by the nl80211 command CmdGetKey
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<KeyInfo, Error> {
+        let mut key = KeyInfo::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211Attr::AttrKeyIdx => key.index = Some(payload.read_u8()?),
+                Nl80211Attr::AttrMac => key.mac = Some(parse_macaddr(&attr.payload)?),
+                Nl80211Attr::AttrKeyCipher => {
+                    key.cipher = Some(CipherSuite::from(payload.read_u32::<LittleEndian>()?))
+                }
+                Nl80211Attr::AttrKeySeq => key.seq = Some(attr.payload.clone()),
+                Nl80211Attr::AttrKey => {
+                    for key_attr in attr.get_nested_attributes::<Nl80211KeyAttributes>()?.iter() {
+                        let mut payload = &key_attr.payload[..];
+                        match key_attr.nla_type {
+                            Nl80211KeyAttributes::KeyIdx => key.index = Some(payload.read_u8()?),
+                            Nl80211KeyAttributes::KeyCipher => {
+                                key.cipher =
+                                    Some(CipherSuite::from(payload.read_u32::<LittleEndian>()?))
+                            }
+                            Nl80211KeyAttributes::KeySeq => {
+                                key.seq = Some(key_attr.payload.clone())
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod test_key {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_pairwise_key_to_attrs() {
+        let key = Key::pairwise(
+            MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]),
+            CipherSuite::Ccmp,
+            vec![0xAA; 16],
+        );
+
+        let attrs = key.to_attrs().unwrap();
+
+        let mut expected_key = vec![20, 0, 1, 0];
+        expected_key.extend(vec![0xAA; 16]);
+        expected_key.extend(vec![5, 0, 2, 0, 0, 0, 0, 0]);
+        expected_key.extend(vec![8, 0, 3, 0, 4, 172, 15, 0]);
+        expected_key.extend(vec![8, 0, 7, 0, 1, 0, 0, 0]);
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![2, 0, 0, 0, 1, 0],
+            },
+            Nlattr {
+                nla_len: 48,
+                nla_type: AttrKey,
+                payload: expected_key,
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
+
+    #[test]
+    fn test_default_key_to_attrs() {
+        let key = DefaultKey {
+            index: 1,
+            management: false,
+            types: vec![Nl80211KeyDefaultTypes::KeyDefaultTypeMulticast],
+        };
+
+        let attrs = key.to_attrs().unwrap();
+
+        let expected_attrs = vec![Nlattr {
+            nla_len: 24,
+            nla_type: AttrKey,
+            payload: vec![5, 0, 2, 0, 1, 0, 0, 0, 4, 0, 5, 0, 8, 0, 8, 0, 4, 0, 2, 0],
+        }];
+
+        assert_eq!(attrs, expected_attrs)
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: AttrKeyIdx,
+                payload: vec![1],
+            },
+            Nlattr {
+                nla_len: 32,
+                nla_type: AttrKey,
+                payload: vec![
+                    5, 0, 2, 0, 1, 0, 0, 0, 8, 0, 3, 0, 4, 172, 15, 0, 10, 0, 4, 0, 5, 0, 0, 0, 0,
+                    0, 0, 0,
+                ],
+            },
+        ];
+
+        let key = KeyInfo::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        let expected_key = KeyInfo {
+            index: Some(1),
+            mac: None,
+            cipher: Some(CipherSuite::Ccmp),
+            seq: Some(vec![5, 0, 0, 0, 0, 0]),
+        };
+
+        assert_eq!(key, expected_key)
+    }
+}
@@ -27,7 +27,7 @@
 //!
 //! use nl80211::Socket;
 //!
-//! fn main() -> Result<(), neli::err::NlError> {
+//! fn main() -> Result<(), nl80211::Error> {
 //!   let interfaces = Socket::connect()?.get_interfaces_info()?;
 //!
 //!   for interface in interfaces {
@@ -50,8 +50,7 @@
 //!       // essid : eduroam
 //!       // mac : FF:FF:FF:FF:FF:FF
 //!       // interface : wlp5s0
-//!       // frequency : 2.412 Ghz
-//!       // channel : 1
+//!       // channel 1 (2412 MHz), width: 20 MHz, center1: 2412 MHz
 //!       // power : 17 dBm
 //!       // phy : 0
 //!       // device : 1
@@ -69,7 +68,7 @@
 //!
 //! use nl80211::Socket;
 //!
-//! fn main() -> Result<(), neli::err::NlError> {
+//! fn main() -> Result<(), nl80211::Error> {
 //!   let interfaces = Socket::connect()?.get_interfaces_info()?;
 //!   for interface in interfaces {
 //!       let station = interface.get_station_info();
@@ -82,8 +81,8 @@
 //!       // average signal : -61 dBm
 //!       // rx packets : 148983
 //!       // tx packets : 46335
-//!       // rx bitrate : 650.0 Mb/s
-//!       // tx bitrate : 866.7 Mb/s
+//!       // rx bitrate : 650.0 MBit/s VHT-MCS 7 80MHz VHT-NSS 2
+//!       // tx bitrate : 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
 //!       // tx retries : 12578
 //!       // tx failed : 2
 //!   }
@@ -94,18 +93,52 @@
 
 mod nl80211traits;
 pub use nl80211traits::*;
+mod error;
+pub use error::*;
 mod cmd;
 pub use cmd::*;
 mod attr;
 pub use attr::*;
 mod helpers;
 mod socket;
-pub use socket::Socket;
+#[cfg(feature = "async")]
+pub use socket::AsyncSocket;
+pub use socket::{Events, Socket};
 mod consts;
 pub use consts::*;
 mod interface;
 pub use interface::*;
 mod station;
 pub use station::*;
+mod rate;
+pub use rate::*;
 mod bss;
 pub use bss::*;
+mod ie;
+pub use ie::*;
+mod wiphy;
+pub use wiphy::*;
+mod channel;
+pub use channel::*;
+mod scan;
+pub use scan::*;
+mod survey;
+pub use survey::*;
+mod regulatory;
+pub use regulatory::*;
+mod regdb;
+pub use regdb::*;
+mod connect;
+pub use connect::*;
+mod key;
+pub use key::*;
+mod mlme;
+pub use mlme::*;
+mod ap;
+pub use ap::*;
+mod cqm;
+pub use cqm::*;
+mod txpower;
+pub use txpower::*;
+mod event;
+pub use event::*;
@@ -0,0 +1,364 @@
+use crate::attr::{Nl80211Attr, Nl80211AuthType, Nl80211Mfp, Nl80211WpaVersions};
+use crate::connect::crypto_attrs;
+use crate::error::Error;
+use crate::ie::{AkmSuite, CipherSuite};
+use crate::nl80211traits::ToNlAttributes;
+use byteorder::{LittleEndian, ReadBytesExt};
+use macaddr::MacAddr;
+use neli::nlattr::Nlattr;
+
+/// Length of the header of IEEE 802.11 management frames
+const MANAGEMENT_HEADER_LEN: usize = 24;
+
+/// Parameters of the nl80211 command CmdAuthenticate, used by userspace SMEs
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthenticateParams {
+    /// BSSID of the access point
+    pub bssid: MacAddr,
+    /// Frequency of the access point in MHz
+    pub frequency: u32,
+    /// SSID of the network
+    pub ssid: String,
+    /// Authentication algorithm
+    pub auth_type: Nl80211AuthType,
+    /// SAE authentication data: transaction sequence number and status code followed by the
+    /// commit or confirm fields
+    pub sae_data: Option<Vec<u8>>,
+    /// Extra information elements added to the authentication frame
+    pub ies: Option<Vec<u8>>,
+}
+
+impl AuthenticateParams {
+    /// Create the parameters of an open system authentication
+    pub fn open(bssid: MacAddr, frequency: u32, ssid: &str) -> AuthenticateParams {
+        AuthenticateParams {
+            bssid,
+            frequency,
+            ssid: ssid.into(),
+            auth_type: Nl80211AuthType::AuthtypeOpenSystem,
+            sae_data: None,
+            ies: None,
+        }
+    }
+
+    /// Create the parameters sending a SAE commit message
+    pub fn sae_commit(
+        bssid: MacAddr,
+        frequency: u32,
+        ssid: &str,
+        commit: &[u8],
+    ) -> AuthenticateParams {
+        AuthenticateParams {
+            sae_data: Some(sae_data(1, commit)),
+            auth_type: Nl80211AuthType::AuthtypeSae,
+            ..AuthenticateParams::open(bssid, frequency, ssid)
+        }
+    }
+
+    /// Create the parameters sending a SAE confirm message
+    pub fn sae_confirm(
+        bssid: MacAddr,
+        frequency: u32,
+        ssid: &str,
+        confirm: &[u8],
+    ) -> AuthenticateParams {
+        AuthenticateParams {
+            sae_data: Some(sae_data(2, confirm)),
+            auth_type: Nl80211AuthType::AuthtypeSae,
+            ..AuthenticateParams::open(bssid, frequency, ssid)
+        }
+    }
+}
+
+/// Build SAE authentication data from a transaction sequence number and a successful status
+fn sae_data(transaction: u16, fields: &[u8]) -> Vec<u8> {
+    let mut data = transaction.to_le_bytes().to_vec();
+    data.extend(&0u16.to_le_bytes());
+    data.extend(fields);
+    data
+}
+
+impl ToNlAttributes for AuthenticateParams {
+    /// Build the attributes of the nl80211 command CmdAuthenticate
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, self.bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphyFreq,
+                self.frequency.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrSsid, self.ssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(&self.auth_type)).to_le_bytes().to_vec(),
+            )?,
+        ];
+
+        if let Some(sae_data) = &self.sae_data {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrSaeData,
+                sae_data.clone(),
+            )?);
+        }
+
+        if let Some(ies) = &self.ies {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrIe, ies.clone())?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Parameters of the nl80211 command CmdAssociate, used by userspace SMEs
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssociateParams {
+    /// BSSID of the access point, the interface must be authenticated with it
+    pub bssid: MacAddr,
+    /// Frequency of the access point in MHz
+    pub frequency: u32,
+    /// SSID of the network
+    pub ssid: String,
+    /// BSSID of the current access point, for reassociations
+    pub prev_bssid: Option<MacAddr>,
+    /// WPA versions in use
+    pub wpa_versions: Vec<Nl80211WpaVersions>,
+    /// Pairwise cipher suites
+    pub pairwise_ciphers: Vec<CipherSuite>,
+    /// Group cipher suite
+    pub group_cipher: Option<CipherSuite>,
+    /// Authentication and key management suites
+    pub akm_suites: Vec<AkmSuite>,
+    /// Management frame protection
+    pub mfp: Option<Nl80211Mfp>,
+    /// Information elements added to the association request (RSN element...)
+    pub ies: Option<Vec<u8>>,
+}
+
+impl AssociateParams {
+    /// Create the parameters of an association with an open network
+    pub fn new(bssid: MacAddr, frequency: u32, ssid: &str) -> AssociateParams {
+        AssociateParams {
+            bssid,
+            frequency,
+            ssid: ssid.into(),
+            prev_bssid: None,
+            wpa_versions: Vec::new(),
+            pairwise_ciphers: Vec::new(),
+            group_cipher: None,
+            akm_suites: Vec::new(),
+            mfp: None,
+            ies: None,
+        }
+    }
+}
+
+impl ToNlAttributes for AssociateParams {
+    /// Build the attributes of the nl80211 command CmdAssociate
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, self.bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphyFreq,
+                self.frequency.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrSsid, self.ssid.as_bytes().to_vec())?,
+        ];
+
+        if let Some(prev_bssid) = &self.prev_bssid {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrPrevBssid,
+                prev_bssid.as_bytes().to_vec(),
+            )?);
+        }
+
+        attrs.extend(crypto_attrs(
+            false,
+            &self.wpa_versions,
+            &self.pairwise_ciphers,
+            self.group_cipher,
+            &self.akm_suites,
+        )?);
+
+        if let Some(mfp) = &self.mfp {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrUseMfp,
+                u32::from(u16::from(mfp)).to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(ies) = &self.ies {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrIe, ies.clone())?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Fixed fields of an IEEE 802.11 authentication frame, as reported by the nl80211 event
+/// CmdAuthenticate
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthenticationFrame {
+    /// Address of the sender
+    pub source: MacAddr,
+    /// BSSID of the access point
+    pub bssid: MacAddr,
+    /// Authentication algorithm number (0 open system, 1 shared key, 3 SAE...)
+    pub algorithm: u16,
+    /// Authentication transaction sequence number (1 commit, 2 confirm for SAE)
+    pub transaction: u16,
+    /// IEEE 802.11 status code, 0 on success
+    pub status: u16,
+    /// Remaining fields and information elements (SAE commit or confirm fields...)
+    pub body: Vec<u8>,
+}
+
+impl AuthenticationFrame {
+    /// Parse an authentication frame, starting with its management header
+    pub fn parse(frame: &[u8]) -> Result<AuthenticationFrame, Error> {
+        if frame.len() < MANAGEMENT_HEADER_LEN + 6 {
+            return Err(Error::Parse("Authentication frame is too short".into()));
+        }
+
+        let mut fields = &frame[MANAGEMENT_HEADER_LEN..];
+        Ok(AuthenticationFrame {
+            source: frame_address(&frame[10..16]),
+            bssid: frame_address(&frame[16..22]),
+            algorithm: fields.read_u16::<LittleEndian>()?,
+            transaction: fields.read_u16::<LittleEndian>()?,
+            status: fields.read_u16::<LittleEndian>()?,
+            body: fields.to_vec(),
+        })
+    }
+}
+
+/// Read an address of a management header
+fn frame_address(bytes: &[u8]) -> MacAddr {
+    MacAddr::from([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]])
+}
+
+#[cfg(test)]
+mod test_mlme {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_sae_commit_to_attrs() {
+        let params = AuthenticateParams::sae_commit(
+            MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]),
+            2412,
+            "home",
+            &[19, 0, 0xAA, 0xBB],
+        );
+
+        let attrs = params.to_attrs().unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![2, 0, 0, 0, 1, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphyFreq,
+                payload: vec![108, 9, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrSsid,
+                payload: vec![104, 111, 109, 101],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrAuthType,
+                payload: vec![4, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrSaeData,
+                payload: vec![1, 0, 0, 0, 19, 0, 0xAA, 0xBB],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
+
+    #[test]
+    fn test_associate_to_attrs() {
+        let params = AssociateParams {
+            pairwise_ciphers: vec![CipherSuite::Ccmp],
+            akm_suites: vec![AkmSuite::Sae],
+            mfp: Some(Nl80211Mfp::MfpRequired),
+            ..AssociateParams::new(
+                MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]),
+                5180,
+                "home",
+            )
+        };
+
+        let attrs = params.to_attrs().unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 10,
+                nla_type: AttrMac,
+                payload: vec![2, 0, 0, 0, 1, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphyFreq,
+                payload: vec![60, 20, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrSsid,
+                payload: vec![104, 111, 109, 101],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrCipherSuitesPairwise,
+                payload: vec![4, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrAkmSuites,
+                payload: vec![8, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrUseMfp,
+                payload: vec![1, 0, 0, 0],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
+
+    #[test]
+    fn test_parse_authentication_frame() {
+        let frame = vec![
+            0xB0, 0x00, 0x3A, 0x01, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0xAA, 0xAA, 0xAA, 0xAA,
+            0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0x10, 0x00, 0x03, 0x00, 0x01, 0x00,
+            0x00, 0x00, 0x13, 0x00,
+        ];
+
+        let frame = AuthenticationFrame::parse(&frame).unwrap();
+
+        let expected_frame = AuthenticationFrame {
+            source: MacAddr::from([0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA]),
+            bssid: MacAddr::from([0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA]),
+            algorithm: 3,
+            transaction: 1,
+            status: 0,
+            body: vec![0x13, 0x00],
+        };
+
+        assert_eq!(frame, expected_frame)
+    }
+}
@@ -1,14 +1,19 @@
 use crate::attr::Nl80211Attr;
-use neli::err::NlError;
-use neli::nlattr::AttrHandle;
+use crate::error::Error;
+use neli::nlattr::{AttrHandle, Nlattr};
 
 /// Construct object by parsing netlink messages attributes returned by a nl80211 command
 pub trait FromNlAttributeHandle {
-    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Self, NlError>
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Self, Error>
     where
         Self: Sized;
 }
 
+/// Build the netlink attributes of a nl80211 command from a request
+pub trait ToNlAttributes {
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error>;
+}
+
 /// Decode netlink payloads (Vec\<u8\>) to appropriate types
 pub trait NlPayloadDecode {
     fn decode(&mut self) -> Self;
@@ -0,0 +1,241 @@
+use crate::attr::{Nl80211ChanWidth, Nl80211RateInfo};
+use crate::error::Error;
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::nlattr::AttrHandle;
+use std::fmt;
+
+/// Transmission or reception rate of a station
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateInfo {
+    /// Bitrate in units of 100 kb/s
+    pub bitrate: Option<u32>,
+    /// HT MCS index
+    pub mcs: Option<u8>,
+    /// VHT MCS index
+    pub vht_mcs: Option<u8>,
+    /// VHT number of spatial streams
+    pub vht_nss: Option<u8>,
+    /// HE MCS index
+    pub he_mcs: Option<u8>,
+    /// HE number of spatial streams
+    pub he_nss: Option<u8>,
+    /// HE guard interval (0: 0.8 µs, 1: 1.6 µs, 2: 3.2 µs)
+    pub he_gi: Option<u8>,
+    /// HE dual carrier modulation
+    pub he_dcm: Option<u8>,
+    /// HE resource unit allocation
+    pub he_ru_alloc: Option<u8>,
+    /// EHT MCS index
+    pub eht_mcs: Option<u8>,
+    /// EHT number of spatial streams
+    pub eht_nss: Option<u8>,
+    /// EHT guard interval (0: 0.8 µs, 1: 1.6 µs, 2: 3.2 µs)
+    pub eht_gi: Option<u8>,
+    /// EHT resource unit allocation
+    pub eht_ru_alloc: Option<u8>,
+    /// Channel width, 20 MHz when not set
+    pub width: Option<Nl80211ChanWidth>,
+    /// Short guard interval (HT and VHT)
+    pub short_gi: bool,
+}
+
+impl RateInfo {
+    /// Parse the nested attributes StaInfoRxBitrate and StaInfoTxBitrate
+    pub(crate) fn from_handle(handle: AttrHandle<Nl80211RateInfo>) -> Result<RateInfo, Error> {
+        let mut rate = RateInfo::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211RateInfo::RateInfoBitrate32 => {
+                    rate.bitrate = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RateInfo::RateInfoBitrate => {
+                    // The 32-bit bitrate is preferred when both are sent
+                    let bitrate = payload.read_u16::<LittleEndian>()?;
+                    rate.bitrate.get_or_insert(u32::from(bitrate));
+                }
+                Nl80211RateInfo::RateInfoMcs => rate.mcs = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoVhtMcs => rate.vht_mcs = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoVhtNss => rate.vht_nss = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoHeMcs => rate.he_mcs = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoHeNss => rate.he_nss = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoHeGi => rate.he_gi = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoHeDcm => rate.he_dcm = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoHeRuAlloc => rate.he_ru_alloc = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoEhtMcs => rate.eht_mcs = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoEhtNss => rate.eht_nss = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoEhtGi => rate.eht_gi = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfoEhtRuAlloc => rate.eht_ru_alloc = Some(payload.read_u8()?),
+                Nl80211RateInfo::RateInfo5MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth5)
+                }
+                Nl80211RateInfo::RateInfo10MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth10)
+                }
+                Nl80211RateInfo::RateInfo40MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth40)
+                }
+                Nl80211RateInfo::RateInfo80MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth80)
+                }
+                Nl80211RateInfo::RateInfo80p80MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth80p80)
+                }
+                Nl80211RateInfo::RateInfo160MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth160)
+                }
+                Nl80211RateInfo::RateInfo320MhzWidth => {
+                    rate.width = Some(Nl80211ChanWidth::ChanWidth320)
+                }
+                Nl80211RateInfo::RateInfoShortGi => rate.short_gi = true,
+                _ => (),
+            }
+        }
+
+        Ok(rate)
+    }
+}
+
+impl fmt::Display for RateInfo {
+    /// Format the rate like iw does
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bitrate {
+            Some(bitrate) if bitrate > 0 => write!(f, "{}.{} MBit/s", bitrate / 10, bitrate % 10)?,
+            _ => write!(f, "(unknown)")?,
+        }
+
+        if let Some(mcs) = self.mcs {
+            write!(f, " MCS {}", mcs)?
+        }
+
+        if let Some(vht_mcs) = self.vht_mcs {
+            write!(f, " VHT-MCS {}", vht_mcs)?
+        }
+
+        match &self.width {
+            Some(Nl80211ChanWidth::ChanWidth5) => write!(f, " 5MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth10) => write!(f, " 10MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth40) => write!(f, " 40MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth80) => write!(f, " 80MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth80p80) => write!(f, " 80P80MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth160) => write!(f, " 160MHz")?,
+            Some(Nl80211ChanWidth::ChanWidth320) => write!(f, " 320MHz")?,
+            _ => (),
+        }
+
+        if self.short_gi {
+            write!(f, " short GI")?
+        }
+
+        if let Some(vht_nss) = self.vht_nss {
+            write!(f, " VHT-NSS {}", vht_nss)?
+        }
+
+        let fields = [
+            ("HE-MCS", self.he_mcs),
+            ("HE-NSS", self.he_nss),
+            ("HE-GI", self.he_gi),
+            ("HE-DCM", self.he_dcm),
+            ("HE-RU-ALLOC", self.he_ru_alloc),
+            ("EHT-MCS", self.eht_mcs),
+            ("EHT-NSS", self.eht_nss),
+            ("EHT-GI", self.eht_gi),
+            ("EHT-RU-ALLOC", self.eht_ru_alloc),
+        ];
+        for (name, value) in fields.iter() {
+            if let Some(value) = value {
+                write!(f, " {} {}", name, value)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_rate {
+    use super::*;
+    use neli::nlattr::Nlattr;
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: Nl80211RateInfo::RateInfoBitrate32,
+                payload: vec![220, 33, 0, 0],
+            },
+            Nlattr {
+                nla_len: 6,
+                nla_type: Nl80211RateInfo::RateInfoBitrate,
+                payload: vec![220, 33],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: Nl80211RateInfo::RateInfoVhtMcs,
+                payload: vec![9],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: Nl80211RateInfo::RateInfoVhtNss,
+                payload: vec![2],
+            },
+            Nlattr {
+                nla_len: 4,
+                nla_type: Nl80211RateInfo::RateInfo80MhzWidth,
+                payload: vec![],
+            },
+            Nlattr {
+                nla_len: 4,
+                nla_type: Nl80211RateInfo::RateInfoShortGi,
+                payload: vec![],
+            },
+        ];
+
+        let rate = RateInfo::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        let expected_rate = RateInfo {
+            bitrate: Some(8668),
+            vht_mcs: Some(9),
+            vht_nss: Some(2),
+            width: Some(Nl80211ChanWidth::ChanWidth80),
+            short_gi: true,
+            ..Default::default()
+        };
+
+        assert_eq!(rate, expected_rate)
+    }
+
+    #[test]
+    fn test_pretty_format() {
+        let rate = RateInfo {
+            bitrate: Some(8667),
+            vht_mcs: Some(9),
+            vht_nss: Some(2),
+            width: Some(Nl80211ChanWidth::ChanWidth80),
+            short_gi: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format!("{}", rate),
+            "866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2"
+        );
+
+        let rate = RateInfo {
+            bitrate: Some(12010),
+            he_mcs: Some(11),
+            he_nss: Some(2),
+            he_gi: Some(0),
+            he_dcm: Some(0),
+            width: Some(Nl80211ChanWidth::ChanWidth80),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format!("{}", rate),
+            "1201.0 MBit/s 80MHz HE-MCS 11 HE-NSS 2 HE-GI 0 HE-DCM 0"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::attr::{Nl80211DfsRegions, Nl80211RegRuleFlags};
+use crate::error::Error;
+use crate::regulatory::{RegulatoryDomain, RegulatoryRule};
+use byteorder::{BigEndian, ReadBytesExt};
+
+/// Magic number of the regulatory database ("RGDB")
+const REGDB_MAGIC: u32 = 0x5247_4442;
+/// Version of the regulatory database format
+const REGDB_VERSION: u32 = 20;
+/// Size of a rule without its optional fields
+const RULE_MIN_LEN: usize = 16;
+
+/// Flags of the rules of the regulatory database, with their nl80211 counterparts
+const RULE_FLAGS: [(u8, Nl80211RegRuleFlags); 5] = [
+    (1 << 0, Nl80211RegRuleFlags::RrfNoOfdm),
+    (1 << 1, Nl80211RegRuleFlags::RrfNoOutdoor),
+    (1 << 2, Nl80211RegRuleFlags::RrfDfs),
+    (1 << 3, Nl80211RegRuleFlags::RrfNoIr),
+    (1 << 4, Nl80211RegRuleFlags::RrfAutoBw),
+];
+
+/// Regulatory database of wireless-regdb (regulatory.db), loaded by the kernel from the firmware
+/// directory
+///
+/// # Example
+///
+/// ```no_run
+/// # use nl80211::RegulatoryDatabase;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>>{
+///   let data = std::fs::read("/lib/firmware/regulatory.db")?;
+///   let database = RegulatoryDatabase::parse(&data)?;
+///
+///   if let Some(domain) = database.country("FR") {
+///       // Channel 100 with a width of 80 MHz
+///       println!("{:?}", domain.check_channel(5530, 80));
+///   }
+/// #   Ok(())
+/// # }
+///```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RegulatoryDatabase {
+    /// Regulatory domain of every country, sorted by country code
+    pub countries: Vec<RegulatoryDomain>,
+}
+
+impl RegulatoryDatabase {
+    /// Parse the content of a regulatory.db file
+    pub fn parse(data: &[u8]) -> Result<RegulatoryDatabase, Error> {
+        let mut header = bytes_at(data, 0, 8)?;
+        if header.read_u32::<BigEndian>()? != REGDB_MAGIC {
+            return Err(Error::Parse("Not a regulatory database".into()));
+        }
+
+        let version = header.read_u32::<BigEndian>()?;
+        if version != REGDB_VERSION {
+            return Err(Error::Parse(format!(
+                "Unsupported regulatory database version {}",
+                version
+            )));
+        }
+
+        let mut countries = Vec::new();
+        // The list of countries ends with an entry without collection
+        for offset in (8..).step_by(4) {
+            let country = bytes_at(data, offset, 4)?;
+            let collection = usize::from((&country[2..]).read_u16::<BigEndian>()?) << 2;
+            if collection == 0 {
+                break;
+            }
+
+            let mut domain = parse_collection(data, collection)?;
+            domain.alpha2 = Some(String::from_utf8_lossy(&country[..2]).to_string());
+            countries.push(domain);
+        }
+
+        Ok(RegulatoryDatabase { countries })
+    }
+
+    /// Regulatory domain of a country, "00" being the world regulatory domain
+    pub fn country(&self, alpha2: &str) -> Option<&RegulatoryDomain> {
+        self.countries.iter().find(
+            |domain| matches!(&domain.alpha2, Some(code) if code.eq_ignore_ascii_case(alpha2)),
+        )
+    }
+}
+
+/// Get a range of the database, failing when it is truncated
+fn bytes_at(data: &[u8], offset: usize, len: usize) -> Result<&[u8], Error> {
+    data.get(offset..offset + len)
+        .ok_or_else(|| Error::Parse(format!("Regulatory database truncated at {}", offset)))
+}
+
+/// Parse a collection of rules, shared by the countries with the same regulatory domain
+fn parse_collection(data: &[u8], offset: usize) -> Result<RegulatoryDomain, Error> {
+    let header = bytes_at(data, offset, 3)?;
+    let (len, rule_count) = (usize::from(header[0]), usize::from(header[1]));
+
+    // Pointers to the rules follow the header aligned to 2 bytes
+    let mut pointers = bytes_at(data, offset + len + (len & 1), rule_count * 2)?;
+    let mut rules = Vec::new();
+    for _ in 0..rule_count {
+        let rule = usize::from(pointers.read_u16::<BigEndian>()?) << 2;
+        rules.push(parse_rule(data, rule)?);
+    }
+
+    Ok(RegulatoryDomain {
+        dfs_region: Some(Nl80211DfsRegions::from(u16::from(header[2]))),
+        rules,
+        ..Default::default()
+    })
+}
+
+/// Parse a rule, with the same values as the kernel reports for it
+fn parse_rule(data: &[u8], offset: usize) -> Result<RegulatoryRule, Error> {
+    let len = usize::from(bytes_at(data, offset, 1)?[0]);
+    if len < RULE_MIN_LEN {
+        return Err(Error::Parse(format!(
+            "Regulatory rule too short at {}",
+            offset
+        )));
+    }
+
+    let mut rule = bytes_at(data, offset, len)?;
+    rule.read_u8()?;
+    let flags = rule.read_u8()?;
+    let max_eirp = rule.read_u16::<BigEndian>()?;
+    let start_freq = rule.read_u32::<BigEndian>()?;
+    let end_freq = rule.read_u32::<BigEndian>()?;
+    let max_bandwidth = rule.read_u32::<BigEndian>()?;
+    // The CAC time is stored in seconds
+    let dfs_cac_time = match rule.read_u16::<BigEndian>() {
+        Ok(cac_time) => u32::from(cac_time) * 1000,
+        Err(_) => 0,
+    };
+
+    let flags: Vec<Nl80211RegRuleFlags> = RULE_FLAGS
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, flag)| flag.clone())
+        .collect();
+
+    Ok(RegulatoryRule {
+        start_freq: Some(start_freq),
+        end_freq: Some(end_freq),
+        max_bandwidth: Some(max_bandwidth),
+        max_antenna_gain: Some(0),
+        max_eirp: Some(u32::from(max_eirp)),
+        dfs_cac_time: Some(dfs_cac_time),
+        raw_flags: flags
+            .iter()
+            .fold(0, |acc, flag| acc | u32::from(u16::from(flag))),
+        flags,
+    })
+}
+
+#[cfg(test)]
+mod test_regdb {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let mut data = vec![0x52, 0x47, 0x44, 0x42, 0, 0, 0, 20];
+        // Country DE using the collection at 52, and the end of the list
+        data.extend(vec![68, 69, 0, 13, 0, 0, 0, 0]);
+        // 2400 - 2483.5 MHz @ 40 MHz, 20 dBm
+        data.extend(vec![
+            16, 0, 7, 208, 0, 36, 159, 0, 0, 37, 229, 44, 0, 0, 156, 64,
+        ]);
+        // 5250 - 5350 MHz @ 80 MHz, 20 dBm, DFS and AUTO-BW, 60 s CAC
+        data.extend(vec![
+            18, 20, 7, 208, 0, 80, 27, 208, 0, 81, 162, 112, 0, 1, 56, 128,
+        ]);
+        data.extend(vec![0, 60, 0, 0]);
+        // Collection of both rules in the ETSI DFS region
+        data.extend(vec![3, 2, 2, 0, 0, 4, 0, 8]);
+
+        let database = RegulatoryDatabase::parse(&data).unwrap();
+
+        let expected_domain = RegulatoryDomain {
+            alpha2: Some("DE".to_string()),
+            dfs_region: Some(Nl80211DfsRegions::DfsEtsi),
+            rules: vec![
+                RegulatoryRule {
+                    start_freq: Some(2_400_000),
+                    end_freq: Some(2_483_500),
+                    max_bandwidth: Some(40_000),
+                    max_antenna_gain: Some(0),
+                    max_eirp: Some(2000),
+                    dfs_cac_time: Some(0),
+                    flags: vec![],
+                    raw_flags: 0,
+                },
+                RegulatoryRule {
+                    start_freq: Some(5_250_000),
+                    end_freq: Some(5_350_000),
+                    max_bandwidth: Some(80_000),
+                    max_antenna_gain: Some(0),
+                    max_eirp: Some(2000),
+                    dfs_cac_time: Some(60_000),
+                    flags: vec![Nl80211RegRuleFlags::RrfDfs, Nl80211RegRuleFlags::RrfAutoBw],
+                    raw_flags: 0x810,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(database.countries, vec![expected_domain]);
+        assert!(database.country("de").is_some());
+        assert!(database.country("FR").is_none());
+    }
+
+    #[test]
+    fn test_invalid_database() {
+        assert!(RegulatoryDatabase::parse(&[0x52, 0x47, 0x44, 0x42, 0, 0, 0, 19]).is_err());
+        assert!(RegulatoryDatabase::parse(&[0x52, 0x47, 0x44, 0x42, 0, 0, 0, 20, 68]).is_err());
+    }
+}
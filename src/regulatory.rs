@@ -0,0 +1,630 @@
+use crate::attr::{
+    Nl80211Attr, Nl80211DfsRegions, Nl80211RegRuleAttr, Nl80211RegRuleFlags,
+    Nl80211UserRegHintType, NlaNested,
+};
+use crate::error::Error;
+use crate::nl80211traits::FromNlAttributeHandle;
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::nlattr::{AttrHandle, Nlattr};
+use std::fmt;
+
+/// Rule flag forbidding 160 MHz channels, not part of `Nl80211RegRuleFlags`
+pub const RRF_NO_160MHZ: u32 = 1 << 16;
+/// Rule flag forbidding HE (802.11ax) operation, not part of `Nl80211RegRuleFlags`
+pub const RRF_NO_HE: u32 = 1 << 17;
+/// Rule flag forbidding 320 MHz channels, not part of `Nl80211RegRuleFlags`
+pub const RRF_NO_320MHZ: u32 = 1 << 18;
+
+/// Regulatory domain returned by the nl80211 command CmdGetReg
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RegulatoryDomain {
+    /// ISO/IEC 3166-1 alpha2 country code, "00" for the world regulatory domain
+    pub alpha2: Option<String>,
+    /// DFS region of the country
+    pub dfs_region: Option<Nl80211DfsRegions>,
+    /// Wiphy owning this private regulatory domain, not set for the global regulatory domain
+    pub wiphy: Option<u32>,
+    /// The wiphy manages its regulatory domain itself, the global one doesn't apply to it
+    pub self_managed: bool,
+    /// Rules of the regulatory domain
+    pub rules: Vec<RegulatoryRule>,
+}
+
+impl FromNlAttributeHandle for RegulatoryDomain {
+    /// Parse netlink messages returned by the nl80211 command CmdGetReg
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<RegulatoryDomain, Error> {
+        let mut domain = RegulatoryDomain::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211Attr::AttrRegAlpha2 => {
+                    domain.alpha2 = Some(
+                        String::from_utf8_lossy(&attr.payload)
+                            .trim_end_matches('\0')
+                            .to_string(),
+                    )
+                }
+                Nl80211Attr::AttrDfsRegion => {
+                    domain.dfs_region = Some(Nl80211DfsRegions::from(u16::from(payload.read_u8()?)))
+                }
+                Nl80211Attr::AttrWiphy => domain.wiphy = Some(payload.read_u32::<LittleEndian>()?),
+                Nl80211Attr::AttrWiphySelfManagedReg => domain.self_managed = true,
+                Nl80211Attr::AttrRegRules => {
+                    for rule in attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        domain
+                            .rules
+                            .push(RegulatoryRule::from_handle(rule.get_nested_attributes()?)?);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(domain)
+    }
+}
+
+impl RegulatoryDomain {
+    /// Check whether a channel may be used, `center_freq` being the center frequency of the
+    /// whole channel in MHz and `width` its width in MHz
+    ///
+    /// Like the kernel, every 20 MHz subchannel must fit in a rule allowing the whole width. The
+    /// restrictions of the subchannels add up and the lowest maximum EIRP applies. Channels
+    /// without width or beyond the range of frequencies can't be used.
+    pub fn check_channel(&self, center_freq: u32, width: u32) -> Option<ChannelPermission> {
+        if width == 0 {
+            return None;
+        }
+
+        let width_khz = width.checked_mul(1000)?;
+        let sub_width = width.min(20) * 1000;
+        let low = center_freq.checked_mul(1000)?.checked_sub(width_khz / 2)?;
+        let mut permission: Option<ChannelPermission> = None;
+
+        for subchannel in 0..(width_khz / sub_width).max(1) {
+            let start = low.checked_add(subchannel * sub_width)?;
+            let end = start.checked_add(sub_width)?;
+            let index = self.rules.iter().position(|rule| {
+                rule.start_freq.unwrap_or_default() <= start
+                    && end <= rule.end_freq.unwrap_or_default()
+            })?;
+            let rule = &self.rules[index];
+
+            if self.max_bandwidth(index) < width_khz {
+                return None;
+            }
+
+            let has_flag = |flag| rule.flags.contains(&flag);
+            let sub_permission = ChannelPermission {
+                max_eirp: rule.max_eirp.unwrap_or_default(),
+                dfs: has_flag(Nl80211RegRuleFlags::RrfDfs),
+                no_ir: has_flag(Nl80211RegRuleFlags::RrfNoIr),
+                no_outdoor: has_flag(Nl80211RegRuleFlags::RrfNoOutdoor),
+                no_indoor: has_flag(Nl80211RegRuleFlags::RrfNoIndoor),
+            };
+            permission = Some(match permission {
+                Some(permission) => ChannelPermission {
+                    max_eirp: permission.max_eirp.min(sub_permission.max_eirp),
+                    dfs: permission.dfs || sub_permission.dfs,
+                    no_ir: permission.no_ir || sub_permission.no_ir,
+                    no_outdoor: permission.no_outdoor || sub_permission.no_outdoor,
+                    no_indoor: permission.no_indoor || sub_permission.no_indoor,
+                },
+                None => sub_permission,
+            });
+        }
+
+        permission
+    }
+
+    /// Maximum bandwidth of a channel within a rule, in kHz
+    ///
+    /// Rules with the AUTO-BW flag allow channels spanning the contiguous rules around them, the
+    /// NO-320MHZ, NO-160MHZ, NO-80MHZ and NO-HT40 flags limit the bandwidth.
+    fn max_bandwidth(&self, index: usize) -> u32 {
+        let rule = &self.rules[index];
+
+        let mut bandwidth = if rule.flags.contains(&Nl80211RegRuleFlags::RrfAutoBw) {
+            let mut start = rule.start_freq.unwrap_or_default();
+            for previous in self.rules[..index].iter().rev() {
+                if previous.end_freq.unwrap_or_default() < start {
+                    break;
+                }
+                start = previous.start_freq.unwrap_or_default();
+            }
+
+            let mut end = rule.end_freq.unwrap_or_default();
+            for next in &self.rules[index + 1..] {
+                if next.start_freq.unwrap_or_default() > end {
+                    break;
+                }
+                end = next.end_freq.unwrap_or_default();
+            }
+
+            end.saturating_sub(start)
+        } else {
+            rule.max_bandwidth.unwrap_or_default()
+        };
+
+        // Same limits as reg_get_max_bandwidth of the kernel
+        if rule.raw_flags & RRF_NO_320MHZ != 0 {
+            bandwidth = bandwidth.min(160_000);
+        }
+        if rule.raw_flags & RRF_NO_160MHZ != 0 {
+            bandwidth = bandwidth.min(80_000);
+        }
+        if rule.flags.contains(&Nl80211RegRuleFlags::RrfNo80mhz) {
+            bandwidth = bandwidth.min(40_000);
+        }
+        if rule.flags.contains(&Nl80211RegRuleFlags::RrfNoHt40minus)
+            && rule.flags.contains(&Nl80211RegRuleFlags::RrfNoHt40plus)
+        {
+            bandwidth = bandwidth.min(20_000);
+        }
+
+        bandwidth
+    }
+
+    /// Compare the rules of two regulatory domains, such as the regulatory database and the
+    /// regulatory domain reported by the kernel
+    pub fn diff(&self, other: &RegulatoryDomain) -> RegulatoryDiff {
+        RegulatoryDiff {
+            dfs_region_changed: self.dfs_region != other.dfs_region,
+            removed: self
+                .rules
+                .iter()
+                .filter(|rule| !other.rules.contains(rule))
+                .cloned()
+                .collect(),
+            added: other
+                .rules
+                .iter()
+                .filter(|rule| !self.rules.contains(rule))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for RegulatoryDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Vec::new();
+
+        match self.wiphy {
+            Some(wiphy) if self.self_managed => {
+                result.push(format!("phy : {} (self-managed)", wiphy))
+            }
+            Some(wiphy) => result.push(format!("phy : {}", wiphy)),
+            None => result.push("phy : global".to_string()),
+        }
+
+        if let Some(alpha2) = &self.alpha2 {
+            result.push(format!("country : {}", alpha2))
+        }
+
+        match &self.dfs_region {
+            Some(Nl80211DfsRegions::DfsFcc) => result.push("dfs region : FCC".to_string()),
+            Some(Nl80211DfsRegions::DfsEtsi) => result.push("dfs region : ETSI".to_string()),
+            Some(Nl80211DfsRegions::DfsJp) => result.push("dfs region : JP".to_string()),
+            _ => result.push("dfs region : unset".to_string()),
+        }
+
+        for rule in &self.rules {
+            result.push(format!("{}", rule))
+        }
+
+        write!(f, "{}", result.join("\n"))
+    }
+}
+
+/// Rule of a regulatory domain, frequencies are in kHz and powers in hundredths of dB
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RegulatoryRule {
+    /// Start of the frequency range
+    pub start_freq: Option<u32>,
+    /// End of the frequency range
+    pub end_freq: Option<u32>,
+    /// Maximum bandwidth of a channel in this range
+    pub max_bandwidth: Option<u32>,
+    /// Maximum antenna gain in mBi
+    pub max_antenna_gain: Option<u32>,
+    /// Maximum EIRP in mBm
+    pub max_eirp: Option<u32>,
+    /// Channel availability check time in milliseconds, for DFS ranges
+    pub dfs_cac_time: Option<u32>,
+    /// Restrictions of the range up to `RrfNo80mhz`
+    pub flags: Vec<Nl80211RegRuleFlags>,
+    /// Every restriction of the range as a bit field, including the flags above `RrfNo80mhz`
+    /// which don't fit in `Nl80211RegRuleFlags` (see the `RRF_*` constants)
+    pub raw_flags: u32,
+}
+
+impl RegulatoryRule {
+    /// Parse a rule nested in the attribute AttrRegRules
+    fn from_handle(handle: AttrHandle<Nl80211RegRuleAttr>) -> Result<RegulatoryRule, Error> {
+        let mut rule = RegulatoryRule::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211RegRuleAttr::AttrRegRuleFlags => {
+                    let flags = payload.read_u32::<LittleEndian>()?;
+                    rule.flags = (0..16)
+                        .filter(|bit| flags & (1 << bit) != 0)
+                        .map(|bit| Nl80211RegRuleFlags::from(1 << bit))
+                        .collect();
+                    rule.raw_flags = flags;
+                }
+                Nl80211RegRuleAttr::AttrFreqRangeStart => {
+                    rule.start_freq = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RegRuleAttr::AttrFreqRangeEnd => {
+                    rule.end_freq = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RegRuleAttr::AttrFreqRangeMaxBw => {
+                    rule.max_bandwidth = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RegRuleAttr::AttrPowerRuleMaxAntGain => {
+                    rule.max_antenna_gain = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RegRuleAttr::AttrPowerRuleMaxEirp => {
+                    rule.max_eirp = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211RegRuleAttr::AttrDfsCacTime => {
+                    rule.dfs_cac_time = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RegulatoryRule {
+    /// Format the rule like iw does
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({} - {} @ {}), ",
+            self.start_freq.unwrap_or_default() / 1000,
+            self.end_freq.unwrap_or_default() / 1000,
+            self.max_bandwidth.unwrap_or_default() / 1000
+        )?;
+
+        match self.max_antenna_gain {
+            Some(gain) if gain > 0 => write!(f, "({}, ", gain / 100)?,
+            _ => write!(f, "(N/A, ")?,
+        }
+        write!(f, "{}), ", self.max_eirp.unwrap_or_default() / 100)?;
+
+        match self.dfs_cac_time {
+            Some(cac_time) if cac_time > 0 => write!(f, "({} ms)", cac_time)?,
+            _ => write!(f, "(N/A)")?,
+        }
+
+        for flag in &self.flags {
+            let name = match flag {
+                Nl80211RegRuleFlags::RrfNoOfdm => "NO-OFDM",
+                Nl80211RegRuleFlags::RrfNoCck => "NO-CCK",
+                Nl80211RegRuleFlags::RrfNoIndoor => "NO-INDOOR",
+                Nl80211RegRuleFlags::RrfNoOutdoor => "NO-OUTDOOR",
+                Nl80211RegRuleFlags::RrfDfs => "DFS",
+                Nl80211RegRuleFlags::RrfPtpOnly => "PTP-ONLY",
+                Nl80211RegRuleFlags::RrfPtmpOnly => "PTMP-ONLY",
+                Nl80211RegRuleFlags::RrfNoIr => "NO-IR",
+                Nl80211RegRuleFlags::RrfNoIbss => "NO-IBSS",
+                Nl80211RegRuleFlags::RrfAutoBw => "AUTO-BW",
+                Nl80211RegRuleFlags::RrfIrConcurrent => "IR-CONCURRENT",
+                Nl80211RegRuleFlags::RrfNoHt40minus => "NO-HT40MINUS",
+                Nl80211RegRuleFlags::RrfNoHt40plus => "NO-HT40PLUS",
+                Nl80211RegRuleFlags::RrfNo80mhz => "NO-80MHZ",
+                Nl80211RegRuleFlags::UnrecognizedVariant(_) => continue,
+            };
+            write!(f, ", {}", name)?
+        }
+
+        let high_flags = [
+            (RRF_NO_160MHZ, "NO-160MHZ"),
+            (RRF_NO_HE, "NO-HE"),
+            (RRF_NO_320MHZ, "NO-320MHZ"),
+        ];
+        for (flag, name) in high_flags.iter() {
+            if self.raw_flags & flag != 0 {
+                write!(f, ", {}", name)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Conditions of use of a channel in a regulatory domain
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChannelPermission {
+    /// Maximum EIRP in mBm
+    pub max_eirp: u32,
+    /// Radar detection is required
+    pub dfs: bool,
+    /// Radiation may not be initiated (no beaconing, no active scan)
+    pub no_ir: bool,
+    /// The channel may not be used outdoor
+    pub no_outdoor: bool,
+    /// The channel may not be used indoor
+    pub no_indoor: bool,
+}
+
+/// Differences between two regulatory domains
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RegulatoryDiff {
+    /// The DFS regions differ
+    pub dfs_region_changed: bool,
+    /// Rules of the first regulatory domain missing from the second one
+    pub removed: Vec<RegulatoryRule>,
+    /// Rules of the second regulatory domain missing from the first one
+    pub added: Vec<RegulatoryRule>,
+}
+
+impl RegulatoryDiff {
+    /// Whether both regulatory domains are the same
+    pub fn is_empty(&self) -> bool {
+        !self.dfs_region_changed && self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+/// Build the attributes of the nl80211 command CmdReqSetReg
+///
+/// Indoor hints don't carry a country code, they tell the kernel the device operates indoor.
+pub(crate) fn regulatory_hint_attrs(
+    alpha2: &str,
+    hint_type: &Nl80211UserRegHintType,
+) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+    let mut attrs = vec![Nlattr::new(
+        None,
+        Nl80211Attr::AttrUserRegHintType,
+        u32::from(u16::from(hint_type)).to_le_bytes().to_vec(),
+    )?];
+
+    if *hint_type == Nl80211UserRegHintType::UserRegHintIndoor {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrRegIndoor,
+            Vec::<u8>::new(),
+        )?);
+        return Ok(attrs);
+    }
+
+    if alpha2.len() != 2 || !alpha2.bytes().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::Invalid(format!(
+            "{:?} is not an alpha2 country code",
+            alpha2
+        )));
+    }
+
+    let mut alpha2 = alpha2.to_ascii_uppercase().into_bytes();
+    alpha2.push(0);
+    attrs.push(Nlattr::new(None, Nl80211Attr::AttrRegAlpha2, alpha2)?);
+
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod test_regulatory {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_parser() {
+        // DFS, AUTO-BW and NO-160MHZ
+        let mut rule = vec![8, 0, 1, 0, 16, 8, 1, 0];
+        rule.extend(vec![8, 0, 2, 0, 208, 27, 80, 0]);
+        rule.extend(vec![8, 0, 3, 0, 112, 162, 81, 0]);
+        rule.extend(vec![8, 0, 4, 0, 128, 56, 1, 0]);
+        rule.extend(vec![8, 0, 5, 0, 0, 0, 0, 0]);
+        rule.extend(vec![8, 0, 6, 0, 52, 8, 0, 0]);
+        rule.extend(vec![8, 0, 7, 0, 96, 234, 0, 0]);
+
+        let mut rules = vec![60, 0, 1, 0];
+        rules.extend(rule);
+
+        let handler = vec![
+            Nlattr {
+                nla_len: 7,
+                nla_type: AttrRegAlpha2,
+                payload: vec![68, 69, 0],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: AttrDfsRegion,
+                payload: vec![2],
+            },
+            Nlattr {
+                nla_len: 64,
+                nla_type: AttrRegRules,
+                payload: rules,
+            },
+        ];
+
+        let domain = RegulatoryDomain::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        let expected_domain = RegulatoryDomain {
+            alpha2: Some("DE".to_string()),
+            dfs_region: Some(Nl80211DfsRegions::DfsEtsi),
+            wiphy: None,
+            self_managed: false,
+            rules: vec![RegulatoryRule {
+                start_freq: Some(5_250_000),
+                end_freq: Some(5_350_000),
+                max_bandwidth: Some(80_000),
+                max_antenna_gain: Some(0),
+                max_eirp: Some(2100),
+                dfs_cac_time: Some(60_000),
+                flags: vec![Nl80211RegRuleFlags::RrfDfs, Nl80211RegRuleFlags::RrfAutoBw],
+                raw_flags: 0x10810,
+            }],
+        };
+
+        assert_eq!(domain, expected_domain)
+    }
+
+    #[test]
+    fn test_pretty_format() {
+        let domain = RegulatoryDomain {
+            alpha2: Some("DE".to_string()),
+            dfs_region: Some(Nl80211DfsRegions::DfsEtsi),
+            wiphy: None,
+            self_managed: false,
+            rules: vec![
+                RegulatoryRule {
+                    start_freq: Some(2_400_000),
+                    end_freq: Some(2_483_500),
+                    max_bandwidth: Some(40_000),
+                    max_antenna_gain: Some(0),
+                    max_eirp: Some(2000),
+                    dfs_cac_time: Some(0),
+                    flags: vec![],
+                    raw_flags: 0,
+                },
+                RegulatoryRule {
+                    start_freq: Some(5_250_000),
+                    end_freq: Some(5_350_000),
+                    max_bandwidth: Some(80_000),
+                    max_antenna_gain: Some(0),
+                    max_eirp: Some(2000),
+                    dfs_cac_time: Some(60_000),
+                    flags: vec![Nl80211RegRuleFlags::RrfDfs, Nl80211RegRuleFlags::RrfAutoBw],
+                    raw_flags: 0x10810,
+                },
+            ],
+        };
+
+        let expected_output = r#"phy : global
+        country : DE
+        dfs region : ETSI
+        (2400 - 2483 @ 40), (N/A, 20), (N/A)
+        (5250 - 5350 @ 80), (N/A, 20), (60000 ms), DFS, AUTO-BW, NO-160MHZ"#;
+
+        assert_eq!(
+            format!("{}", domain),
+            expected_output.replace("\n        ", "\n")
+        )
+    }
+
+    fn test_domain() -> RegulatoryDomain {
+        let rule =
+            |start_freq: u32, end_freq: u32, max_bandwidth: u32, flags: Vec<_>| RegulatoryRule {
+                start_freq: Some(start_freq),
+                end_freq: Some(end_freq),
+                max_bandwidth: Some(max_bandwidth),
+                max_antenna_gain: Some(0),
+                max_eirp: Some(2000),
+                dfs_cac_time: Some(0),
+                raw_flags: flags
+                    .iter()
+                    .fold(0, |acc, flag| acc | u32::from(u16::from(flag))),
+                flags,
+            };
+
+        RegulatoryDomain {
+            alpha2: Some("DE".to_string()),
+            dfs_region: Some(Nl80211DfsRegions::DfsEtsi),
+            rules: vec![
+                rule(2_400_000, 2_483_500, 40_000, vec![]),
+                rule(
+                    5_150_000,
+                    5_250_000,
+                    80_000,
+                    vec![
+                        Nl80211RegRuleFlags::RrfNoOutdoor,
+                        Nl80211RegRuleFlags::RrfAutoBw,
+                    ],
+                ),
+                rule(
+                    5_250_000,
+                    5_350_000,
+                    80_000,
+                    vec![
+                        Nl80211RegRuleFlags::RrfNoOutdoor,
+                        Nl80211RegRuleFlags::RrfDfs,
+                        Nl80211RegRuleFlags::RrfAutoBw,
+                    ],
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_channel() {
+        let domain = test_domain();
+
+        // Channel 1
+        assert_eq!(
+            domain.check_channel(2412, 20),
+            Some(ChannelPermission {
+                max_eirp: 2000,
+                ..Default::default()
+            })
+        );
+        // Channel 50, 160 MHz over two contiguous AUTO-BW rules
+        assert_eq!(
+            domain.check_channel(5250, 160),
+            Some(ChannelPermission {
+                max_eirp: 2000,
+                dfs: true,
+                no_outdoor: true,
+                ..Default::default()
+            })
+        );
+        // 80 MHz wider than the 2.4 GHz rule allows
+        assert_eq!(domain.check_channel(2442, 80), None);
+        // 160 MHz forbidden by the kernel
+        let mut domain = test_domain();
+        domain.rules[2].raw_flags |= RRF_NO_160MHZ;
+        assert_eq!(domain.check_channel(5250, 160), None);
+        assert!(domain.check_channel(5290, 80).is_some());
+        // Channel 144, out of every rule
+        assert_eq!(domain.check_channel(5720, 20), None);
+        // Invalid channels
+        assert_eq!(domain.check_channel(2412, 0), None);
+        assert_eq!(domain.check_channel(u32::MAX, 20), None);
+        assert_eq!(domain.check_channel(5250, u32::MAX), None);
+        assert_eq!(domain.check_channel(4_294_967, 20), None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let domain = test_domain();
+        let mut kernel_domain = test_domain();
+        kernel_domain.rules[0].max_eirp = Some(2300);
+
+        let diff = domain.diff(&kernel_domain);
+
+        assert!(!diff.dfs_region_changed);
+        assert_eq!(diff.removed, vec![domain.rules[0].clone()]);
+        assert_eq!(diff.added, vec![kernel_domain.rules[0].clone()]);
+        assert!(domain.diff(&test_domain()).is_empty());
+    }
+
+    #[test]
+    fn test_regulatory_hint_attrs() {
+        let attrs = regulatory_hint_attrs("de", &Nl80211UserRegHintType::UserRegHintUser).unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrUserRegHintType,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 7,
+                nla_type: AttrRegAlpha2,
+                payload: vec![68, 69, 0],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs);
+        assert!(regulatory_hint_attrs("DEU", &Nl80211UserRegHintType::UserRegHintUser).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::attr::{Nl80211Attr, Nl80211ScanFlags, NlaNested};
+use crate::error::Error;
+use crate::nl80211traits::ToNlAttributes;
+use neli::nlattr::Nlattr;
+
+/// Parameters of a scan triggered with the nl80211 command CmdTriggerScan
+///
+/// The default request is a passive scan on every supported channel.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScanRequest {
+    /// SSIDs to probe for. An empty SSID probes for any network, no SSID at all makes the scan passive
+    pub ssids: Vec<String>,
+    /// Frequencies to scan in MHz, every supported frequency is scanned when empty
+    pub frequencies: Vec<u32>,
+    /// Extra information elements added to the probe requests
+    pub ies: Option<Vec<u8>>,
+    /// Scan flags (flush, low priority, random MAC address...)
+    pub flags: Vec<Nl80211ScanFlags>,
+}
+
+impl ScanRequest {
+    /// Create a request for an active scan probing for any network
+    pub fn active() -> ScanRequest {
+        ScanRequest {
+            ssids: vec![String::new()],
+            ..Default::default()
+        }
+    }
+}
+
+impl ToNlAttributes for ScanRequest {
+    /// Build the attributes of the nl80211 command CmdTriggerScan
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = Vec::new();
+
+        if !self.ssids.is_empty() {
+            let mut ssids = Nlattr::new(None, Nl80211Attr::AttrScanSsids, Vec::<u8>::new())?;
+            for (index, ssid) in self.ssids.iter().enumerate() {
+                let nla_type = NlaNested::from(index as u16 + 1);
+                ssids.add_nested_attribute(&Nlattr::new(
+                    None,
+                    nla_type,
+                    ssid.as_bytes().to_vec(),
+                )?)?;
+            }
+            attrs.push(ssids);
+        }
+
+        if !self.frequencies.is_empty() {
+            let mut frequencies =
+                Nlattr::new(None, Nl80211Attr::AttrScanFrequencies, Vec::<u8>::new())?;
+            for (index, frequency) in self.frequencies.iter().enumerate() {
+                let nla_type = NlaNested::from(index as u16 + 1);
+                frequencies.add_nested_attribute(&Nlattr::new(
+                    None,
+                    nla_type,
+                    frequency.to_le_bytes().to_vec(),
+                )?)?;
+            }
+            attrs.push(frequencies);
+        }
+
+        if let Some(ies) = &self.ies {
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrIe, ies.clone())?);
+        }
+
+        if !self.flags.is_empty() {
+            let flags = self
+                .flags
+                .iter()
+                .fold(0u32, |acc, flag| acc | u32::from(u16::from(flag)));
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrScanFlags,
+                flags.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test_scan {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_passive_scan() {
+        let attrs = ScanRequest::default().to_attrs().unwrap();
+
+        assert!(attrs.is_empty())
+    }
+
+    #[test]
+    fn test_to_attrs() {
+        let request = ScanRequest {
+            ssids: vec!["".into(), "eduroam".into()],
+            frequencies: vec![2412, 5180],
+            ies: None,
+            flags: vec![
+                Nl80211ScanFlags::ScanFlagFlush,
+                Nl80211ScanFlags::ScanFlagRandomAddr,
+            ],
+        };
+
+        let attrs = request.to_attrs().unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 20,
+                nla_type: AttrScanSsids,
+                payload: vec![4, 0, 1, 0, 11, 0, 2, 0, 101, 100, 117, 114, 111, 97, 109, 0],
+            },
+            Nlattr {
+                nla_len: 20,
+                nla_type: AttrScanFrequencies,
+                payload: vec![8, 0, 1, 0, 108, 9, 0, 0, 8, 0, 2, 0, 60, 20, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrScanFlags,
+                payload: vec![10, 0, 0, 0],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
+}
@@ -0,0 +1,1026 @@
+use super::{
+    connect_aborted, connect_timed_out, decode, decode_event, explain_interface_busy, join,
+    key_selector, merge_wiphy, open, request, resolve_multicast_group, scan_aborted,
+    scan_timed_out, set_interface_attrs, split_datagram, Received, CONNECT_TIMEOUT, SCAN_TIMEOUT,
+};
+use crate::ap::ApConfig;
+use crate::attr::{Nl80211Attr, Nl80211Iftype, Nl80211PsState, Nl80211UserRegHintType};
+use crate::bss::Bss;
+use crate::cmd::Nl80211Cmd;
+use crate::connect::{ConnectParams, ConnectResult};
+use crate::cqm::CqmConfig;
+use crate::error::Error;
+use crate::event::{Event, MulticastGroup};
+use crate::interface::{Interface, InterfaceOptions};
+use crate::key::{DefaultKey, Key, KeyInfo};
+use crate::mlme::{AssociateParams, AuthenticateParams};
+use crate::nl80211traits::{FromNlAttributeHandle, ToNlAttributes};
+use crate::regulatory::{regulatory_hint_attrs, RegulatoryDomain};
+use crate::scan::ScanRequest;
+use crate::station::{Station, StationParams};
+use crate::survey::Survey;
+use crate::txpower::TxPower;
+use crate::wiphy::Wiphy;
+use futures_util::stream::{self, Stream};
+use macaddr::MacAddr;
+use neli::consts::{NlmF, Nlmsg};
+use neli::err::NlError;
+use neli::genl::Genlmsghdr;
+use neli::nl::Nlmsghdr;
+use neli::nlattr::Nlattr;
+use neli::socket::NlSocket;
+use neli::MAX_NL_LENGTH;
+use std::collections::VecDeque;
+use tokio::io::unix::AsyncFd;
+
+/// A nl80211 socket driven by the tokio reactor
+///
+/// Every command of [`Socket`](struct.Socket.html) has an asynchronous counterpart returning a
+/// future, with the same parameters and behavior. The socket must be created from within a tokio
+/// runtime.
+pub struct AsyncSocket {
+    sock: AsyncFd<NlSocket>,
+    pub family_id: u16,
+    /// Messages of the last datagram not processed yet
+    messages: VecDeque<Nlmsghdr<Nlmsg, Vec<u8>>>,
+    /// Notifications received while waiting for the reply of a command
+    notifications: VecDeque<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>,
+}
+
+impl AsyncSocket {
+    /// Create a new nl80211 socket registered to the tokio reactor
+    pub fn connect() -> Result<Self, Error> {
+        let (mut nl80211sock, family_id) = open()?;
+        nl80211sock.nonblock().map_err(NlError::from)?;
+
+        Ok(Self {
+            sock: AsyncFd::new(nl80211sock).map_err(NlError::from)?,
+            family_id,
+            messages: VecDeque::new(),
+            notifications: VecDeque::new(),
+        })
+    }
+
+    /// Get information for all your wifi interfaces
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::AsyncSocket;
+    ///
+    /// # async fn run() -> Result<(), nl80211::Error>{
+    ///     let wifi_interfaces = AsyncSocket::connect()?.get_interfaces_info().await?;
+    ///     for wifi_interface in wifi_interfaces {
+    ///         println!("{}", wifi_interface);
+    ///     }
+    /// #   Ok(())
+    /// # }
+    /// # fn main() {}
+    ///```
+    pub async fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, Error> {
+        self.send(
+            Nl80211Cmd::CmdGetInterface,
+            vec![NlmF::Request, NlmF::Dump],
+            vec![],
+        )?;
+        self.recv_dump().await
+    }
+
+    /// Create a virtual interface on a wiphy
+    pub async fn new_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        options: &InterfaceOptions,
+    ) -> Result<Interface, Error> {
+        let mut attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrWiphy, phy.to_le_bytes().to_vec())?,
+            Nlattr::new(None, Nl80211Attr::AttrIfname, name)?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIftype,
+                u32::from(u16::from(iftype)).to_le_bytes().to_vec(),
+            )?,
+        ];
+        attrs.extend(options.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdNewInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        let interface = match self.recv().await? {
+            Some(response) => Interface::from_handle(response.get_attr_handle())?,
+            None => return Err(Error::Parse("Interface reply is missing".into())),
+        };
+        self.recv_ack().await?;
+
+        Ok(interface)
+    }
+
+    /// Change the type and settings of an existing interface
+    pub async fn set_interface(
+        &mut self,
+        interface_attr_if_index: u32,
+        iftype: Option<Nl80211Iftype>,
+        options: &InterfaceOptions,
+    ) -> Result<(), Error> {
+        let attrs = set_interface_attrs(interface_attr_if_index, iftype, options)?;
+
+        self.send(
+            Nl80211Cmd::CmdSetInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await.map_err(explain_interface_busy)
+    }
+
+    /// Delete a virtual interface
+    pub async fn del_interface(&mut self, interface_attr_if_index: u32) -> Result<(), Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdDelInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Get the capabilities of every wireless hardware device
+    pub async fn get_wiphys(&mut self) -> Result<Vec<Wiphy>, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrSplitWiphyDump,
+            Vec::<u8>::new(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetWiphy,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+
+        let mut wiphys = Vec::new();
+        while let Some(response) = self.recv().await? {
+            merge_wiphy(&mut wiphys, response.get_attr_handle())?;
+        }
+
+        Ok(wiphys)
+    }
+
+    /// Get access point information for a specific interface
+    pub async fn get_station_info(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<Station, Error> {
+        Ok(self
+            .get_stations(interface_attr_if_index)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    /// Get every station (remote peer) known by an interface
+    pub async fn get_stations(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<Vec<Station>, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetStation,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+        self.recv_dump().await
+    }
+
+    /// Get a single station of an interface from its MAC address
+    pub async fn get_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: MacAddr,
+    ) -> Result<Station, Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrMac, mac.as_bytes().to_vec())?,
+        ];
+
+        self.send(Nl80211Cmd::CmdGetStation, vec![NlmF::Request], attrs)?;
+        match self.recv().await? {
+            Some(response) => Station::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Station reply is missing".into())),
+        }
+    }
+
+    /// Get the first BSS of the last scan results of an interface
+    pub async fn get_bss_info(&mut self, interface_attr_if_index: u32) -> Result<Bss, Error> {
+        Ok(self
+            .get_scan_results(interface_attr_if_index)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    /// Get every BSS found by the last scans of an interface
+    pub async fn get_scan_results(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<Vec<Bss>, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetScan,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+        self.recv_dump().await
+    }
+
+    /// Trigger a scan on an interface without waiting for its results
+    pub async fn trigger_scan(
+        &mut self,
+        interface_attr_if_index: u32,
+        request: &ScanRequest,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(request.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdTriggerScan,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Scan for networks on an interface and wait for the results
+    pub async fn scan(
+        &mut self,
+        interface_attr_if_index: u32,
+        request: &ScanRequest,
+    ) -> Result<Vec<Bss>, Error> {
+        // Listen to scan notifications before triggering the scan so that none can be missed
+        let mut notifications = AsyncSocket::connect()?;
+        notifications.subscribe(&[MulticastGroup::Scan])?;
+
+        self.trigger_scan(interface_attr_if_index, request).await?;
+
+        let ifindex = Some(interface_attr_if_index);
+        let done = async {
+            loop {
+                let notification = notifications.recv_notification().await?;
+
+                // Notifications this crate can't decode don't end the wait
+                match decode_event(notification) {
+                    Ok(Event::NewScanResults { ifindex: index }) if index == ifindex => {
+                        return Ok(())
+                    }
+                    Ok(Event::ScanAborted { ifindex: index }) if index == ifindex => {
+                        return Err(scan_aborted())
+                    }
+                    _ => (),
+                }
+            }
+        };
+
+        tokio::time::timeout(SCAN_TIMEOUT, done)
+            .await
+            .unwrap_or_else(|_| Err(scan_timed_out()))?;
+
+        self.get_scan_results(interface_attr_if_index).await
+    }
+
+    /// Get the survey of every channel of an interface
+    pub async fn get_survey(&mut self, interface_attr_if_index: u32) -> Result<Vec<Survey>, Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrSurveyRadioStats, Vec::<u8>::new())?,
+        ];
+
+        self.send(
+            Nl80211Cmd::CmdGetSurvey,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+        self.recv_dump().await
+    }
+
+    /// Get the regulatory domain of a wiphy, or the global regulatory domain
+    pub async fn get_regulatory(&mut self, wiphy: Option<u32>) -> Result<RegulatoryDomain, Error> {
+        let mut attrs = Vec::new();
+        if let Some(wiphy) = wiphy {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphy,
+                wiphy.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        self.send(Nl80211Cmd::CmdGetReg, vec![NlmF::Request], attrs)?;
+        match self.recv().await? {
+            Some(response) => RegulatoryDomain::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Regulatory domain reply is missing".into())),
+        }
+    }
+
+    /// Ask the kernel to apply the regulatory domain of a country
+    pub async fn request_regulatory(
+        &mut self,
+        alpha2: &str,
+        hint_type: Nl80211UserRegHintType,
+    ) -> Result<(), Error> {
+        let attrs = regulatory_hint_attrs(alpha2, &hint_type)?;
+
+        self.send(
+            Nl80211Cmd::CmdReqSetReg,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Connect an interface to a network with the in-kernel SME and wait for the result
+    pub async fn connect_network(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &ConnectParams,
+    ) -> Result<ConnectResult, Error> {
+        // Listen to MLME notifications before connecting so that the result can't be missed
+        let mut notifications = AsyncSocket::connect()?;
+        notifications.subscribe(&[MulticastGroup::Mlme])?;
+
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdConnect,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await?;
+
+        let ifindex = Some(interface_attr_if_index);
+        let result = async {
+            loop {
+                let notification = notifications.recv_notification().await?;
+
+                // Notifications this crate can't decode don't end the wait
+                match decode_event(notification) {
+                    Ok(Event::Connect {
+                        ifindex: index,
+                        bssid,
+                        status,
+                        req_ie,
+                        resp_ie,
+                    }) if index == ifindex => {
+                        return Ok(ConnectResult {
+                            bssid,
+                            status,
+                            req_ie,
+                            resp_ie,
+                        })
+                    }
+                    Ok(Event::Disconnect {
+                        ifindex: index,
+                        reason,
+                        ..
+                    }) if index == ifindex => return Err(connect_aborted(reason)),
+                    _ => (),
+                }
+            }
+        };
+
+        tokio::time::timeout(CONNECT_TIMEOUT, result)
+            .await
+            .unwrap_or_else(|_| Err(connect_timed_out()))
+    }
+
+    /// Disconnect an interface from its network with an IEEE 802.11 reason code
+    pub async fn disconnect(
+        &mut self,
+        interface_attr_if_index: u32,
+        reason: u16,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+
+        self.send(
+            Nl80211Cmd::CmdDisconnect,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Authenticate an interface with an access point, for drivers without in-kernel SME
+    pub async fn authenticate(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &AuthenticateParams,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdAuthenticate,
+            interface_attr_if_index,
+            params.to_attrs()?,
+        )
+        .await
+    }
+
+    /// Associate an authenticated interface with an access point, for drivers without in-kernel
+    /// SME
+    pub async fn associate(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &AssociateParams,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdAssociate,
+            interface_attr_if_index,
+            params.to_attrs()?,
+        )
+        .await
+    }
+
+    /// Deauthenticate an interface from an access point with an IEEE 802.11 reason code
+    pub async fn deauthenticate(
+        &mut self,
+        interface_attr_if_index: u32,
+        bssid: MacAddr,
+        reason: u16,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+        self.send_mlme(
+            Nl80211Cmd::CmdDeauthenticate,
+            interface_attr_if_index,
+            attrs,
+        )
+        .await
+    }
+
+    /// Disassociate an interface from an access point with an IEEE 802.11 reason code
+    pub async fn disassociate(
+        &mut self,
+        interface_attr_if_index: u32,
+        bssid: MacAddr,
+        reason: u16,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+        self.send_mlme(Nl80211Cmd::CmdDisassociate, interface_attr_if_index, attrs)
+            .await
+    }
+
+    /// Start an access point on an interface
+    pub async fn start_ap(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &ApConfig,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdStartAp,
+            interface_attr_if_index,
+            config.to_attrs()?,
+        )
+        .await
+    }
+
+    /// Replace the beacon of a running access point with the beacon of its configuration
+    pub async fn update_beacon(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &ApConfig,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdSetBeacon,
+            interface_attr_if_index,
+            config.protected_beacon()?.to_attrs()?,
+        )
+        .await
+    }
+
+    /// Stop the access point running on an interface
+    pub async fn stop_ap(&mut self, interface_attr_if_index: u32) -> Result<(), Error> {
+        self.send_mlme(Nl80211Cmd::CmdStopAp, interface_attr_if_index, vec![])
+            .await
+    }
+
+    /// Add a station to an access point, usually once it is associated
+    pub async fn new_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: MacAddr,
+        params: &StationParams,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrMac,
+            mac.as_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send_mlme(Nl80211Cmd::CmdNewStation, interface_attr_if_index, attrs)
+            .await
+    }
+
+    /// Change the parameters of a station, e.g. authorize it once the 4-way handshake completed
+    pub async fn set_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: MacAddr,
+        params: &StationParams,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrMac,
+            mac.as_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send_mlme(Nl80211Cmd::CmdSetStation, interface_attr_if_index, attrs)
+            .await
+    }
+
+    /// Remove a station from an access point, or every station when no MAC address is given
+    pub async fn del_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: Option<MacAddr>,
+        reason: Option<u16>,
+    ) -> Result<(), Error> {
+        let mut attrs = Vec::new();
+
+        if let Some(mac) = mac {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMac,
+                mac.as_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(reason) = reason {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        self.send_mlme(Nl80211Cmd::CmdDelStation, interface_attr_if_index, attrs)
+            .await
+    }
+
+    /// Install an encryption key on an interface
+    pub async fn new_key(&mut self, interface_attr_if_index: u32, key: &Key) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(key.to_attrs()?);
+
+        self.send(Nl80211Cmd::CmdNewKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack().await
+    }
+
+    /// Select the default key of an interface
+    pub async fn set_default_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        key: &DefaultKey,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(key.to_attrs()?);
+
+        self.send(Nl80211Cmd::CmdSetKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack().await
+    }
+
+    /// Get the cipher and sequence counter of a key, pairwise keys are selected by the peer MAC
+    /// address
+    pub async fn get_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        index: u8,
+        mac: Option<MacAddr>,
+    ) -> Result<KeyInfo, Error> {
+        let attrs = key_selector(interface_attr_if_index, index, mac)?;
+
+        self.send(Nl80211Cmd::CmdGetKey, vec![NlmF::Request], attrs)?;
+        match self.recv().await? {
+            Some(response) => KeyInfo::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Key reply is missing".into())),
+        }
+    }
+
+    /// Delete a key, pairwise keys are selected by the peer MAC address
+    pub async fn del_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        index: u8,
+        mac: Option<MacAddr>,
+    ) -> Result<(), Error> {
+        let attrs = key_selector(interface_attr_if_index, index, mac)?;
+
+        self.send(Nl80211Cmd::CmdDelKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack().await
+    }
+
+    /// Resolve the id of a nl80211 multicast group with the generic netlink controller
+    pub fn resolve_multicast_group(&self, group: MulticastGroup) -> Result<u32, Error> {
+        resolve_multicast_group(group)
+    }
+
+    /// Configure the connection quality monitor of an interface
+    pub async fn set_cqm(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &CqmConfig,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(config.to_attrs()?);
+
+        self.send(Nl80211Cmd::CmdSetCqm, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack().await
+    }
+
+    /// Get the power save state of an interface
+    pub async fn get_power_save(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<Nl80211PsState, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(Nl80211Cmd::CmdGetPowerSave, vec![NlmF::Request], attrs)?;
+        match self.recv().await? {
+            Some(response) => Nl80211PsState::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Power save reply is missing".into())),
+        }
+    }
+
+    /// Enable or disable power save on an interface
+    pub async fn set_power_save(
+        &mut self,
+        interface_attr_if_index: u32,
+        state: Nl80211PsState,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrPsState,
+                u32::from(u16::from(state)).to_le_bytes().to_vec(),
+            )?,
+        ];
+
+        self.send(
+            Nl80211Cmd::CmdSetPowerSave,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Set the transmit power of a wiphy
+    pub async fn set_wiphy_tx_power(&mut self, wiphy: u32, power: &TxPower) -> Result<(), Error> {
+        self.check_tx_power(wiphy, None, power).await?;
+
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrWiphy,
+            wiphy.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(power.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdSetWiphy,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Set the transmit power of an interface
+    pub async fn set_tx_power(
+        &mut self,
+        interface_attr_if_index: u32,
+        power: &TxPower,
+    ) -> Result<(), Error> {
+        if power.level().is_some() {
+            let interface = self
+                .get_interfaces_info()
+                .await?
+                .into_iter()
+                .find(|interface| interface.index == Some(interface_attr_if_index));
+            if let Some(Interface {
+                phy: Some(phy),
+                frequency,
+                ..
+            }) = interface
+            {
+                self.check_tx_power(phy, frequency, power).await?;
+            }
+        }
+
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(power.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdSetWiphy,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().await
+    }
+
+    /// Check a transmit power level against the maximum power of the channels of a wiphy
+    async fn check_tx_power(
+        &mut self,
+        wiphy: u32,
+        frequency: Option<u32>,
+        power: &TxPower,
+    ) -> Result<(), Error> {
+        if power.level().is_none() {
+            return Ok(());
+        }
+
+        match self
+            .get_wiphys()
+            .await?
+            .iter()
+            .find(|candidate| candidate.index == Some(wiphy))
+        {
+            Some(wiphy) => power.check(wiphy, frequency),
+            None => Ok(()),
+        }
+    }
+
+    /// Join nl80211 multicast groups to receive their notifications
+    pub fn subscribe(&mut self, groups: &[MulticastGroup]) -> Result<(), Error> {
+        join(self.sock.get_ref(), groups)
+    }
+
+    /// Wait for the next notification of the joined multicast groups
+    pub async fn recv_event(&mut self) -> Result<Event, Error> {
+        let notification = self.recv_notification().await?;
+        decode_event(notification)
+    }
+
+    /// Wait for the next notification message of the joined multicast groups
+    async fn recv_notification(&mut self) -> Result<Genlmsghdr<Nl80211Cmd, Nl80211Attr>, Error> {
+        loop {
+            if let Some(notification) = self.notifications.pop_front() {
+                return Ok(notification);
+            }
+
+            // Replies left over by a previous command are dropped
+            if let Received::Notification(message) = self.recv_message().await? {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// Stream the notifications of the joined multicast groups
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{AsyncSocket, MulticastGroup};
+    /// # use futures_util::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), nl80211::Error>{
+    ///   let mut socket = AsyncSocket::connect()?;
+    ///   socket.subscribe(&[MulticastGroup::Mlme])?;
+    ///
+    ///   let mut events = Box::pin(socket.events());
+    ///   while let Some(event) = events.next().await {
+    ///       println!("{:?}", event?);
+    ///   }
+    /// #   Ok(())
+    /// # }
+    /// # fn main() {}
+    ///```
+    pub fn events(&mut self) -> impl Stream<Item = Result<Event, Error>> + '_ {
+        stream::unfold(self, |socket| async move {
+            let event = socket.recv_event().await;
+            Some((event, socket))
+        })
+    }
+
+    /// Send a nl80211 command
+    ///
+    /// Netlink requests are handled synchronously by the kernel, sending never blocks.
+    fn send(
+        &mut self,
+        cmd: Nl80211Cmd,
+        flags: Vec<NlmF>,
+        attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+    ) -> Result<(), Error> {
+        let nlhdr = request(self.family_id, cmd, flags, attrs)?;
+        self.sock.get_mut().send_nl(nlhdr)?;
+        Ok(())
+    }
+
+    /// Send a command to an interface and wait for the kernel to accept it
+    async fn send_mlme(
+        &mut self,
+        cmd: Nl80211Cmd,
+        interface_attr_if_index: u32,
+        params: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(params);
+
+        self.send(cmd, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack().await
+    }
+
+    /// Wait for the kernel to acknowledge the last command
+    async fn recv_ack(&mut self) -> Result<(), Error> {
+        while self.recv().await?.is_some() {}
+        Ok(())
+    }
+
+    /// Receive and parse every message of a dump until the kernel signals its end
+    async fn recv_dump<T: FromNlAttributeHandle>(&mut self) -> Result<Vec<T>, Error> {
+        let mut results = Vec::new();
+
+        while let Some(response) = self.recv().await? {
+            results.push(T::from_handle(response.get_attr_handle())?);
+        }
+
+        Ok(results)
+    }
+
+    /// Receive the next nl80211 message
+    ///
+    /// Returns `None` once the kernel ends the exchange (end of a dump or acknowledgment) and an
+    /// error when the kernel rejected the request.
+    async fn recv(&mut self) -> Result<Option<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, Error> {
+        loop {
+            match self.recv_message().await? {
+                Received::Reply(message) => return Ok(Some(message)),
+                Received::End => return Ok(None),
+                Received::Notification(message) => self.notifications.push_back(message),
+                Received::Noop => (),
+            }
+        }
+    }
+
+    /// Receive and decode a single netlink message
+    async fn recv_message(&mut self) -> Result<Received, Error> {
+        let response = loop {
+            match self.messages.pop_front() {
+                Some(response) => break response,
+                None => {
+                    let datagram = self.recv_datagram().await?;
+                    self.messages.extend(split_datagram(datagram)?);
+                }
+            }
+        };
+
+        decode(response)
+    }
+
+    /// Wait until the kernel sends a datagram and read it
+    async fn recv_datagram(&mut self) -> Result<Vec<u8>, Error> {
+        loop {
+            let mut guard = self.sock.readable().await.map_err(NlError::from)?;
+
+            let mut datagram = vec![0; MAX_NL_LENGTH];
+            match guard.try_io(|sock| sock.get_ref().recv(&mut datagram, 0)) {
+                Ok(Ok(0)) => {
+                    return Err(NlError::new("No data could be read from the socket").into())
+                }
+                Ok(Ok(len)) => {
+                    datagram.truncate(len);
+                    return Ok(datagram);
+                }
+                Ok(Err(err)) => return Err(NlError::from(err).into()),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_asynchronous {
+    use super::super::test_socket::{ifindex_attr, nl_socket_pair, push_message};
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn test_recv_event() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let (mut sock, fd) = nl_socket_pair();
+            sock.nonblock().unwrap();
+            let mut socket = AsyncSocket {
+                sock: AsyncFd::new(sock).unwrap(),
+                family_id: 0x1c,
+                messages: VecDeque::new(),
+                notifications: VecDeque::new(),
+            };
+
+            // A reply left over by a previous command is dropped
+            push_message(fd, 1, Nl80211Cmd::CmdGetInterface, vec![ifindex_attr(2)]);
+            push_message(fd, 0, Nl80211Cmd::CmdNewScanResults, vec![ifindex_attr(3)]);
+            push_message(fd, 0, Nl80211Cmd::CmdScanAborted, vec![ifindex_attr(3)]);
+
+            assert_eq!(
+                socket.recv_event().await.unwrap(),
+                Event::NewScanResults { ifindex: Some(3) }
+            );
+            let mut events = Box::pin(socket.events());
+            assert_eq!(
+                events.next().await.unwrap().unwrap(),
+                Event::ScanAborted { ifindex: Some(3) }
+            );
+
+            unsafe { libc::close(fd) };
+        });
+    }
+}
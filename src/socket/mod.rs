@@ -1,22 +1,61 @@
+use crate::ap::ApConfig;
 use crate::bss::Bss;
-use crate::nl80211traits::FromNlAttributeHandle;
-use crate::station::Station;
+use crate::connect::{ConnectParams, ConnectResult};
+use crate::nl80211traits::{FromNlAttributeHandle, ToNlAttributes};
+use crate::regulatory::{regulatory_hint_attrs, RegulatoryDomain};
+use crate::scan::ScanRequest;
+use crate::station::{Station, StationParams};
+use crate::survey::Survey;
+use crate::txpower::TxPower;
+use crate::wiphy::Wiphy;
 // use crate::station::parse_station;
 use crate::attr::Nl80211Attr;
+use crate::attr::Nl80211Iftype;
+use crate::attr::Nl80211PsState;
+use crate::attr::Nl80211UserRegHintType;
 use crate::cmd::Nl80211Cmd;
 use crate::consts::{NL_80211_GENL_NAME, NL_80211_GENL_VERSION};
-use crate::interface::Interface;
+use crate::cqm::CqmConfig;
+use crate::error::{Error, KernelError};
+use crate::event::{Event, MulticastGroup};
+use crate::interface::{Interface, InterfaceOptions};
+use crate::key::{DefaultKey, Key, KeyInfo};
+use crate::mlme::{AssociateParams, AuthenticateParams};
+use macaddr::MacAddr;
 use neli::consts::{NlFamily, NlmF, Nlmsg};
 use neli::err::NlError;
 use neli::genl::Genlmsghdr;
 use neli::nl::Nlmsghdr;
-use neli::nlattr::Nlattr;
+use neli::nlattr::{AttrHandle, Nlattr};
 use neli::socket::NlSocket;
+use neli::{Nl, StreamReadBuffer, MAX_NL_LENGTH};
+use std::collections::VecDeque;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncSocket;
+
+/// Socket option enabling extended ACK reporting (NETLINK_EXT_ACK)
+const NETLINK_EXT_ACK: libc::c_int = 11;
+
+/// Time to wait for the result of a connection
+pub(crate) const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time to wait for the end of a scan
+pub(crate) const SCAN_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// A generic netlink socket to send commands and receive messages
 pub struct Socket {
     pub sock: NlSocket,
     pub family_id: u16,
+    /// Notifications received while waiting for the reply of a command
+    notifications: VecDeque<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>,
+    /// Messages of the last datagram which haven't been decoded yet
+    messages: VecDeque<Nlmsghdr<Nlmsg, Vec<u8>>>,
 }
 
 impl Socket {
@@ -32,7 +71,7 @@ impl Socket {
     /// # use neli::err::NlError;
     /// # use neli::consts::{NlFamily,NlmF,Nlmsg};
     ///
-    /// # fn main() -> Result<(), neli::err::NlError> {
+    /// # fn main() -> Result<(), nl80211::Error> {
     ///     // Create a new nl80211 socket and use this socket to send nl80211 commands
     ///     let mut nl80211sock = Socket::connect()?;
     ///
@@ -69,20 +108,14 @@ impl Socket {
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn connect() -> Result<Self, neli::err::NlError> {
-        let family_id =
-            NlSocket::new(NlFamily::Generic, true)?.resolve_genl_family(NL_80211_GENL_NAME)?;
-
-        let track_seq = true;
-        let mut nl80211sock = NlSocket::new(NlFamily::Generic, track_seq)?;
-
-        let pid = None;
-        let groups = None;
-        nl80211sock.bind(pid, groups)?;
+    pub fn connect() -> Result<Self, Error> {
+        let (nl80211sock, family_id) = open()?;
 
         Ok(Self {
             sock: nl80211sock,
             family_id,
+            notifications: VecDeque::new(),
+            messages: VecDeque::new(),
         })
     }
 
@@ -93,7 +126,7 @@ impl Socket {
     /// ```no_run
     /// # use nl80211::Socket;
     ///
-    /// # fn main() -> Result<(), neli::err::NlError>{
+    /// # fn main() -> Result<(), nl80211::Error>{
     ///     let wifi_interfaces = Socket::connect()?.get_interfaces_info();
     ///     for wifi_interface in wifi_interfaces? {
     ///         println!("{}", wifi_interface);
@@ -101,54 +134,163 @@ impl Socket {
     /// #   Ok(())
     /// # }
     ///```
-    pub fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, neli::err::NlError> {
-        let mut interfaces = Vec::new();
-        let nl80211sock = &mut self.sock;
-
-        let attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>> = vec![];
-        let genlhdr = Genlmsghdr::new(Nl80211Cmd::CmdGetInterface, NL_80211_GENL_VERSION, attrs)?;
-
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = vec![NlmF::Request, NlmF::Dump];
-            let seq = None;
-            let pid = None;
-            let payload = genlhdr;
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
+    pub fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, Error> {
+        self.send(
+            Nl80211Cmd::CmdGetInterface,
+            vec![NlmF::Request, NlmF::Dump],
+            vec![],
+        )?;
+        self.recv_dump()
+    }
+
+    /// Create a virtual interface on top of a wiphy
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{InterfaceOptions, Nl80211Iftype, Nl80211MntrFlags, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let options = InterfaceOptions {
+    ///       monitor_flags: vec![Nl80211MntrFlags::MntrFlagOtherBss],
+    ///       ..Default::default()
+    ///   };
+    ///   let interface =
+    ///       Socket::connect()?.new_interface(0, "mon0", Nl80211Iftype::IftypeMonitor, &options)?;
+    ///   println!("{}", interface);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn new_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        options: &InterfaceOptions,
+    ) -> Result<Interface, Error> {
+        let mut attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrWiphy, phy.to_le_bytes().to_vec())?,
+            Nlattr::new(None, Nl80211Attr::AttrIfname, name)?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIftype,
+                u32::from(u16::from(iftype)).to_le_bytes().to_vec(),
+            )?,
+        ];
+        attrs.extend(options.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdNewInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        let interface = match self.recv()? {
+            Some(response) => Interface::from_handle(response.get_attr_handle())?,
+            None => return Err(Error::Parse("Interface reply is missing".into())),
         };
+        self.recv_ack()?;
+
+        Ok(interface)
+    }
+
+    /// Change the type and settings of an existing interface
+    ///
+    /// The type of an interface can only be changed while it is down, the kernel rejects the
+    /// request with EBUSY otherwise. The MAC address can't be changed by nl80211, options setting
+    /// it are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{InterfaceOptions, Nl80211Iftype, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let options = InterfaceOptions {
+    ///       four_address: Some(true),
+    ///       ..Default::default()
+    ///   };
+    ///   Socket::connect()?.set_interface(3, Some(Nl80211Iftype::IftypeStation), &options)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_interface(
+        &mut self,
+        interface_attr_if_index: u32,
+        iftype: Option<Nl80211Iftype>,
+        options: &InterfaceOptions,
+    ) -> Result<(), Error> {
+        let attrs = set_interface_attrs(interface_attr_if_index, iftype, options)?;
 
-        nl80211sock.send_nl(nlhdr)?;
+        self.send(
+            Nl80211Cmd::CmdSetInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack().map_err(explain_interface_busy)
+    }
 
-        let mut iter = nl80211sock.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>();
+    /// Delete a virtual interface
+    pub fn del_interface(&mut self, interface_attr_if_index: u32) -> Result<(), Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
 
-        while let Some(Ok(response)) = iter.next() {
-            match response.nl_type {
-                Nlmsg::Error => {
-                    return Err(NlError::Msg(format!(
-                        "Received error response from socket: {:?}",
-                        response
-                    )))
-                }
-                Nlmsg::Done => break,
-                _ => {
-                    let handle = response.nl_payload.get_attr_handle();
-                    interfaces.push(Interface::from_handle(handle)?);
-                }
-            };
+        self.send(
+            Nl80211Cmd::CmdDelInterface,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack()
+    }
+
+    /// Get the capabilities of every wireless hardware device
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   for wiphy in Socket::connect()?.get_wiphys()? {
+    ///       println!("{}\n", wiphy);
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_wiphys(&mut self) -> Result<Vec<Wiphy>, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrSplitWiphyDump,
+            Vec::<u8>::new(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetWiphy,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+
+        let mut wiphys = Vec::new();
+        while let Some(response) = self.recv()? {
+            merge_wiphy(&mut wiphys, response.get_attr_handle())?;
         }
 
-        Ok(interfaces)
+        Ok(wiphys)
     }
 
     /// Get access point information for a specific interface
     ///
+    /// On a station interface the only peer is the access point, use
+    /// [`get_stations`](#method.get_stations) to list every peer of an AP interface.
+    ///
     /// # Example
     ///
     /// ```no_run
     /// # use nl80211::Socket;
     ///
-    /// # fn main() -> Result<(), neli::err::NlError>{
+    /// # fn main() -> Result<(), nl80211::Error>{
     ///   // First of all we need to get wifi interface information to get more data
     ///   let wifi_interfaces = Socket::connect()?.get_interfaces_info();
     ///   for wifi_interface in wifi_interfaces? {
@@ -162,104 +304,1596 @@ impl Socket {
     /// #   Ok(())
     /// # }
     ///```
-    pub fn get_station_info(
+    pub fn get_station_info(&mut self, interface_attr_if_index: u32) -> Result<Station, Error> {
+        Ok(self
+            .get_stations(interface_attr_if_index)?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    /// Get every station (remote peer) known by an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let mut socket = Socket::connect()?;
+    ///   // List the clients associated with an access point interface
+    ///   for station in socket.get_stations(3)? {
+    ///       println!("{}\n", station);
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_stations(&mut self, interface_attr_if_index: u32) -> Result<Vec<Station>, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetStation,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+        self.recv_dump()
+    }
+
+    /// Get a single station of an interface from its MAC address
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    /// # use macaddr::MacAddr;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let peer = MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]);
+    ///   let station = Socket::connect()?.get_station(3, peer)?;
+    ///   println!("{}", station);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_station(
         &mut self,
         interface_attr_if_index: u32,
-    ) -> Result<Station, neli::err::NlError> {
-        let nl80211sock = &mut self.sock;
+        mac: MacAddr,
+    ) -> Result<Station, Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrMac, mac.as_bytes().to_vec())?,
+        ];
+
+        self.send(Nl80211Cmd::CmdGetStation, vec![NlmF::Request], attrs)?;
+        match self.recv()? {
+            Some(response) => Station::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Station reply is missing".into())),
+        }
+    }
 
-        let mut attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>> = vec![];
-        let interface_attr_if_index = interface_attr_if_index.to_le_bytes();
-        let new_attr = Nlattr::new(
+    /// Get the first BSS of the last scan results of an interface
+    ///
+    /// Use [`get_scan_results`](#method.get_scan_results) to list every visible network.
+    pub fn get_bss_info(&mut self, interface_attr_if_index: u32) -> Result<Bss, Error> {
+        Ok(self
+            .get_scan_results(interface_attr_if_index)?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    /// Get every BSS found by the last scans of an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let wifi_interfaces = Socket::connect()?.get_interfaces_info();
+    ///   for wifi_interface in wifi_interfaces? {
+    ///     if let Some(netlink_index) = wifi_interface.index {
+    ///       for bss in Socket::connect()?.get_scan_results(netlink_index)? {
+    ///           println!("{}\n", bss);
+    ///       }
+    ///     }
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_scan_results(&mut self, interface_attr_if_index: u32) -> Result<Vec<Bss>, Error> {
+        let attrs = vec![Nlattr::new(
             None,
             Nl80211Attr::AttrIfindex,
-            interface_attr_if_index.to_vec(),
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(
+            Nl80211Cmd::CmdGetScan,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
         )?;
-        attrs.push(new_attr);
-
-        let genlhdr = Genlmsghdr::new(Nl80211Cmd::CmdGetStation, NL_80211_GENL_VERSION, attrs)?;
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = vec![NlmF::Request, NlmF::Dump];
-            let seq = None;
-            let pid = None;
-            let payload = genlhdr;
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+        self.recv_dump()
+    }
 
-        nl80211sock.send_nl(nlhdr)?;
+    /// Trigger a scan on an interface without waiting for its results
+    ///
+    /// The kernel notifies the end of the scan on the "scan" multicast group, results can then be
+    /// fetched with [`get_scan_results`](#method.get_scan_results).
+    pub fn trigger_scan(
+        &mut self,
+        interface_attr_if_index: u32,
+        request: &ScanRequest,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(request.to_attrs()?);
 
-        let mut iter = nl80211sock.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>();
+        self.send(
+            Nl80211Cmd::CmdTriggerScan,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack()
+    }
 
-        if let Some(Ok(response)) = iter.next() {
-            match response.nl_type {
-                Nlmsg::Error => {
-                    return Err(NlError::Msg(format!(
-                        "Received error response from socket: {:?}",
-                        response
-                    )))
-                }
-                Nlmsg::Done => (),
-                _ => {
-                    let handle = response.nl_payload.get_attr_handle();
-                    return Station::from_handle(handle);
-                }
+    /// Scan for networks on an interface and wait for the results
+    ///
+    /// The scan fails if the kernel doesn't report its end within 60 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{ScanRequest, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let results = Socket::connect()?.scan(3, &ScanRequest::active())?;
+    ///   for bss in results {
+    ///       println!("{}\n", bss);
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn scan(
+        &mut self,
+        interface_attr_if_index: u32,
+        request: &ScanRequest,
+    ) -> Result<Vec<Bss>, Error> {
+        // Listen to scan notifications before triggering the scan so that none can be missed
+        let mut notifications = Socket::connect()?;
+        notifications.subscribe(&[MulticastGroup::Scan])?;
+
+        self.trigger_scan(interface_attr_if_index, request)?;
+
+        notifications.wait_scan_done(interface_attr_if_index)?;
+
+        self.get_scan_results(interface_attr_if_index)
+    }
+
+    /// Wait for the end of a scan on a socket listening to scan notifications
+    fn wait_scan_done(&mut self, interface_attr_if_index: u32) -> Result<(), Error> {
+        let ifindex = Some(interface_attr_if_index);
+        let deadline = Instant::now() + SCAN_TIMEOUT;
+        loop {
+            let notification = match self.recv_notification_before(deadline)? {
+                Some(notification) => notification,
+                None => return Err(scan_timed_out()),
             };
+
+            // Notifications this crate can't decode don't end the wait
+            match decode_event(notification) {
+                Ok(Event::NewScanResults { ifindex: index }) if index == ifindex => return Ok(()),
+                Ok(Event::ScanAborted { ifindex: index }) if index == ifindex => {
+                    return Err(scan_aborted())
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Get the survey of every channel of an interface
+    ///
+    /// The whole radio statistics are requested as well, drivers supporting them return an extra
+    /// survey without frequency.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   // Find the least busy channel
+    ///   let quietest = Socket::connect()?
+    ///       .get_survey(3)?
+    ///       .into_iter()
+    ///       .filter(|survey| !survey.is_radio_stats())
+    ///       .filter_map(|survey| Some((survey.frequency?, survey.utilization()?)))
+    ///       .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    ///   println!("{:?}", quietest);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_survey(&mut self, interface_attr_if_index: u32) -> Result<Vec<Survey>, Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(None, Nl80211Attr::AttrSurveyRadioStats, Vec::<u8>::new())?,
+        ];
+
+        self.send(
+            Nl80211Cmd::CmdGetSurvey,
+            vec![NlmF::Request, NlmF::Dump],
+            attrs,
+        )?;
+        self.recv_dump()
+    }
+
+    /// Get the regulatory domain of a wiphy, or the global regulatory domain
+    ///
+    /// The global regulatory domain is returned for wiphys without a private regulatory domain.
+    /// Self-managed wiphys only follow their own regulatory domain.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::Socket;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let domain = Socket::connect()?.get_regulatory(None)?;
+    ///   println!("{}", domain);
+    ///
+    ///   // phy : global
+    ///   // country : DE
+    ///   // dfs region : ETSI
+    ///   // (2400 - 2483 @ 40), (N/A, 20), (N/A)
+    ///   // (5150 - 5250 @ 80), (N/A, 23), (N/A), NO-OUTDOOR, AUTO-BW
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_regulatory(&mut self, wiphy: Option<u32>) -> Result<RegulatoryDomain, Error> {
+        let mut attrs = Vec::new();
+        if let Some(wiphy) = wiphy {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphy,
+                wiphy.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        self.send(Nl80211Cmd::CmdGetReg, vec![NlmF::Request], attrs)?;
+        match self.recv()? {
+            Some(response) => RegulatoryDomain::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Regulatory domain reply is missing".into())),
         }
-        Ok(Station::default())
     }
 
-    pub fn get_bss_info(
+    /// Ask the kernel to apply the regulatory domain of a country
+    ///
+    /// The kernel applies the request asynchronously and notifies the new regulatory domain on
+    /// the "regulatory" multicast group. Self-managed wiphys receive the hint and decide on their
+    /// own whether to follow it. The country code is ignored by indoor hints.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{Nl80211UserRegHintType, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   Socket::connect()?.request_regulatory("FR", Nl80211UserRegHintType::UserRegHintUser)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn request_regulatory(
         &mut self,
-        interface_attr_if_index: u32,
-    ) -> Result<Bss, neli::err::NlError> {
-        let nl80211sock = &mut self.sock;
+        alpha2: &str,
+        hint_type: Nl80211UserRegHintType,
+    ) -> Result<(), Error> {
+        let attrs = regulatory_hint_attrs(alpha2, &hint_type)?;
 
-        let mut attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>> = vec![];
+        self.send(
+            Nl80211Cmd::CmdReqSetReg,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack()
+    }
+
+    /// Connect an interface to a network with the in-kernel SME and wait for the result
+    ///
+    /// The result is returned whether the connection succeeded or not, its status code tells
+    /// why the access point rejected the connection. The connection fails if the interface is
+    /// disconnected first, or if the kernel doesn't report the result within 30 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{ConnectParams, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let params = ConnectParams {
+    ///       ssid: "eduroam".into(),
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   let result = Socket::connect()?.connect_network(3, &params)?;
+    ///   println!("connected : {}", result.is_success());
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn connect_network(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &ConnectParams,
+    ) -> Result<ConnectResult, Error> {
+        // Listen to MLME notifications before connecting so that the result can't be missed
+        let mut notifications = Socket::connect()?;
+        notifications.subscribe(&[MulticastGroup::Mlme])?;
 
-        let interface_attr_if_index = interface_attr_if_index.to_le_bytes();
-        let new_attr = Nlattr::new(
+        let mut attrs = vec![Nlattr::new(
             None,
             Nl80211Attr::AttrIfindex,
-            interface_attr_if_index.to_vec(),
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdConnect,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
         )?;
-        attrs.push(new_attr);
-
-        let genlhdr = Genlmsghdr::new(Nl80211Cmd::CmdGetScan, NL_80211_GENL_VERSION, attrs)?;
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = vec![NlmF::Request, NlmF::Dump];
-            let seq = None;
-            let pid = None;
-            let payload = genlhdr;
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+        self.recv_ack()?;
 
-        nl80211sock.send_nl(nlhdr)?;
+        notifications.wait_connect_result(interface_attr_if_index)
+    }
 
-        let mut iter = nl80211sock.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>();
+    /// Wait for the result of a connection on a socket listening to MLME notifications
+    fn wait_connect_result(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<ConnectResult, Error> {
+        let ifindex = Some(interface_attr_if_index);
+        let deadline = Instant::now() + CONNECT_TIMEOUT;
+        loop {
+            let notification = match self.recv_notification_before(deadline)? {
+                Some(notification) => notification,
+                None => return Err(connect_timed_out()),
+            };
 
-        if let Some(Ok(response)) = iter.next() {
-            match response.nl_type {
-                Nlmsg::Error => {
-                    return Err(NlError::Msg(format!(
-                        "Received error response from socket: {:?}",
-                        response
-                    )))
-                }
-                Nlmsg::Done => (),
-                _ => {
-                    let handle = response.nl_payload.get_attr_handle();
-                    return Bss::from_handle(handle);
+            // Notifications this crate can't decode don't end the wait
+            match decode_event(notification) {
+                Ok(Event::Connect {
+                    ifindex: index,
+                    bssid,
+                    status,
+                    req_ie,
+                    resp_ie,
+                }) if index == ifindex => {
+                    return Ok(ConnectResult {
+                        bssid,
+                        status,
+                        req_ie,
+                        resp_ie,
+                    })
                 }
+                Ok(Event::Disconnect {
+                    ifindex: index,
+                    reason,
+                    ..
+                }) if index == ifindex => return Err(connect_aborted(reason)),
+                _ => (),
             }
         }
-        Ok(Bss::default())
     }
 
-    // pub fn scan(&mut self) -> Result<(), neli::err::NlError> {
-    //     Ok(())
-    // }
+    /// Disconnect an interface from its network with an IEEE 802.11 reason code
+    pub fn disconnect(&mut self, interface_attr_if_index: u32, reason: u16) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrIfindex,
+                interface_attr_if_index.to_le_bytes().to_vec(),
+            )?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+
+        self.send(
+            Nl80211Cmd::CmdDisconnect,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack()
+    }
+
+    /// Authenticate an interface with an access point, for drivers without in-kernel SME
+    ///
+    /// The kernel reports the authentication frame of the access point (or a timeout) on the "mlme"
+    /// multicast group, [`subscribe`](#method.subscribe) to it before authenticating.
+    pub fn authenticate(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &AuthenticateParams,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdAuthenticate,
+            interface_attr_if_index,
+            params.to_attrs()?,
+        )
+    }
+
+    /// Associate an authenticated interface with an access point, for drivers without in-kernel
+    /// SME
+    ///
+    /// The kernel reports the association response (or a timeout) on the "mlme" multicast group.
+    pub fn associate(
+        &mut self,
+        interface_attr_if_index: u32,
+        params: &AssociateParams,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdAssociate,
+            interface_attr_if_index,
+            params.to_attrs()?,
+        )
+    }
+
+    /// Deauthenticate an interface from an access point with an IEEE 802.11 reason code
+    pub fn deauthenticate(
+        &mut self,
+        interface_attr_if_index: u32,
+        bssid: MacAddr,
+        reason: u16,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+        self.send_mlme(
+            Nl80211Cmd::CmdDeauthenticate,
+            interface_attr_if_index,
+            attrs,
+        )
+    }
+
+    /// Disassociate an interface from an access point with an IEEE 802.11 reason code
+    pub fn disassociate(
+        &mut self,
+        interface_attr_if_index: u32,
+        bssid: MacAddr,
+        reason: u16,
+    ) -> Result<(), Error> {
+        let attrs = vec![
+            Nlattr::new(None, Nl80211Attr::AttrMac, bssid.as_bytes().to_vec())?,
+            Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?,
+        ];
+        self.send_mlme(Nl80211Cmd::CmdDisassociate, interface_attr_if_index, attrs)
+    }
+
+    /// Start an access point on an interface
+    ///
+    /// The interface must be of type AP and down before being configured, the kernel brings it up.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{ApConfig, Beacon, Socket};
+    /// # use macaddr::MacAddr;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let bssid = MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]);
+    ///   let beacon = Beacon {
+    ///       channel: Some(1),
+    ///       ..Beacon::new(bssid, "test")
+    ///   };
+    ///
+    ///   Socket::connect()?.start_ap(3, &ApConfig::new(beacon))?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn start_ap(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &ApConfig,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdStartAp,
+            interface_attr_if_index,
+            config.to_attrs()?,
+        )
+    }
+
+    /// Replace the beacon of a running access point with the beacon of its configuration
+    ///
+    /// The beacon advertises the security of the configuration like
+    /// [`start_ap`](#method.start_ap) does (see
+    /// [`ApConfig::protected_beacon`](struct.ApConfig.html#method.protected_beacon)).
+    pub fn update_beacon(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &ApConfig,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdSetBeacon,
+            interface_attr_if_index,
+            config.protected_beacon()?.to_attrs()?,
+        )
+    }
+
+    /// Stop the access point running on an interface
+    pub fn stop_ap(&mut self, interface_attr_if_index: u32) -> Result<(), Error> {
+        self.send_mlme(Nl80211Cmd::CmdStopAp, interface_attr_if_index, vec![])
+    }
+
+    /// Add a station to an access point, usually once it is associated
+    pub fn new_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: MacAddr,
+        params: &StationParams,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrMac,
+            mac.as_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send_mlme(Nl80211Cmd::CmdNewStation, interface_attr_if_index, attrs)
+    }
+
+    /// Change the parameters of a station, e.g. authorize it once the 4-way handshake completed
+    pub fn set_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: MacAddr,
+        params: &StationParams,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrMac,
+            mac.as_bytes().to_vec(),
+        )?];
+        attrs.extend(params.to_attrs()?);
+
+        self.send_mlme(Nl80211Cmd::CmdSetStation, interface_attr_if_index, attrs)
+    }
+
+    /// Remove a station from an access point, or every station when no MAC address is given
+    ///
+    /// The stations are deauthenticated with the reason code when one is given.
+    pub fn del_station(
+        &mut self,
+        interface_attr_if_index: u32,
+        mac: Option<MacAddr>,
+        reason: Option<u16>,
+    ) -> Result<(), Error> {
+        let mut attrs = Vec::new();
+
+        if let Some(mac) = mac {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrMac,
+                mac.as_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(reason) = reason {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrReasonCode,
+                reason.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        self.send_mlme(Nl80211Cmd::CmdDelStation, interface_attr_if_index, attrs)
+    }
+
+    /// Install an encryption key on an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{CipherSuite, Key, Socket};
+    /// # use macaddr::MacAddr;
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let peer = MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]);
+    ///   let key = Key::pairwise(peer, CipherSuite::Ccmp, vec![0; 16]);
+    ///
+    ///   Socket::connect()?.new_key(3, &key)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn new_key(&mut self, interface_attr_if_index: u32, key: &Key) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(key.to_attrs()?);
+
+        self.send(Nl80211Cmd::CmdNewKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack()
+    }
+
+    /// Select the default key of an interface
+    pub fn set_default_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        key: &DefaultKey,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(key.to_attrs()?);
+
+        self.send(Nl80211Cmd::CmdSetKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack()
+    }
+
+    /// Get the cipher and sequence counter of a key, pairwise keys are selected by the peer MAC
+    /// address
+    pub fn get_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        index: u8,
+        mac: Option<MacAddr>,
+    ) -> Result<KeyInfo, Error> {
+        let attrs = key_selector(interface_attr_if_index, index, mac)?;
+
+        self.send(Nl80211Cmd::CmdGetKey, vec![NlmF::Request], attrs)?;
+        match self.recv()? {
+            Some(response) => KeyInfo::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Key reply is missing".into())),
+        }
+    }
+
+    /// Delete a key, pairwise keys are selected by the peer MAC address
+    pub fn del_key(
+        &mut self,
+        interface_attr_if_index: u32,
+        index: u8,
+        mac: Option<MacAddr>,
+    ) -> Result<(), Error> {
+        let attrs = key_selector(interface_attr_if_index, index, mac)?;
+
+        self.send(Nl80211Cmd::CmdDelKey, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack()
+    }
+
+    /// Configure the connection quality monitor of an interface
+    ///
+    /// Notifications are sent as [`Event::NotifyCqm`](enum.Event.html#variant.NotifyCqm) to the
+    /// "mlme" multicast group.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{CqmConfig, Event, MulticastGroup, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let mut socket = Socket::connect()?;
+    ///   let config = CqmConfig::Rssi {
+    ///       thresholds: vec![-75],
+    ///       hysteresis: 4,
+    ///   };
+    ///   socket.set_cqm(3, &config)?;
+    ///   socket.subscribe(&[MulticastGroup::Mlme])?;
+    ///
+    ///   for event in socket.events() {
+    ///       if let Event::NotifyCqm { cqm, .. } = event? {
+    ///           println!("{:?} at {:?} dBm", cqm.rssi_threshold_event, cqm.rssi_level);
+    ///       }
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_cqm(
+        &mut self,
+        interface_attr_if_index: u32,
+        config: &CqmConfig,
+    ) -> Result<(), Error> {
+        self.send_mlme(
+            Nl80211Cmd::CmdSetCqm,
+            interface_attr_if_index,
+            config.to_attrs()?,
+        )
+    }
+
+    /// Get the power save state of an interface
+    pub fn get_power_save(
+        &mut self,
+        interface_attr_if_index: u32,
+    ) -> Result<Nl80211PsState, Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+
+        self.send(Nl80211Cmd::CmdGetPowerSave, vec![NlmF::Request], attrs)?;
+        match self.recv()? {
+            Some(response) => Nl80211PsState::from_handle(response.get_attr_handle()),
+            None => Err(Error::Parse("Power save reply is missing".into())),
+        }
+    }
+
+    /// Enable or disable power save on an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{Nl80211PsState, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   Socket::connect()?.set_power_save(3, Nl80211PsState::PsEnabled)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_power_save(
+        &mut self,
+        interface_attr_if_index: u32,
+        state: Nl80211PsState,
+    ) -> Result<(), Error> {
+        let attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrPsState,
+            u32::from(u16::from(state)).to_le_bytes().to_vec(),
+        )?];
+
+        self.send_mlme(Nl80211Cmd::CmdSetPowerSave, interface_attr_if_index, attrs)
+    }
+
+    /// Set the transmit power of a wiphy
+    ///
+    /// The power level is checked against the maximum power of the enabled channels of the wiphy,
+    /// when the wiphy reports it. The current power level is reported per interface, see
+    /// [`Interface::power`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{Socket, TxPower};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   // Limit the transmit power of phy0 to 15 dBm
+    ///   Socket::connect()?.set_wiphy_tx_power(0, &TxPower::Limited(1500))?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    ///
+    /// [`Interface::power`]: struct.Interface.html#structfield.power
+    pub fn set_wiphy_tx_power(&mut self, wiphy: u32, power: &TxPower) -> Result<(), Error> {
+        self.check_tx_power(wiphy, None, power)?;
+
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrWiphy,
+            wiphy.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(power.to_attrs()?);
+
+        self.send(
+            Nl80211Cmd::CmdSetWiphy,
+            vec![NlmF::Request, NlmF::Ack],
+            attrs,
+        )?;
+        self.recv_ack()
+    }
+
+    /// Set the transmit power of an interface
+    ///
+    /// The power level is checked against the maximum power of the current channel of the
+    /// interface, or of the enabled channels of its wiphy when the interface isn't operating.
+    /// Drivers without per interface transmit power apply the setting to the whole wiphy.
+    pub fn set_tx_power(
+        &mut self,
+        interface_attr_if_index: u32,
+        power: &TxPower,
+    ) -> Result<(), Error> {
+        if power.level().is_some() {
+            let interface = self
+                .get_interfaces_info()?
+                .into_iter()
+                .find(|interface| interface.index == Some(interface_attr_if_index));
+            if let Some(Interface {
+                phy: Some(phy),
+                frequency,
+                ..
+            }) = interface
+            {
+                self.check_tx_power(phy, frequency, power)?;
+            }
+        }
+
+        self.send_mlme(
+            Nl80211Cmd::CmdSetWiphy,
+            interface_attr_if_index,
+            power.to_attrs()?,
+        )
+    }
+
+    /// Check a transmit power level against the maximum power of the channels of a wiphy
+    fn check_tx_power(
+        &mut self,
+        wiphy: u32,
+        frequency: Option<u32>,
+        power: &TxPower,
+    ) -> Result<(), Error> {
+        if power.level().is_none() {
+            return Ok(());
+        }
+
+        match self
+            .get_wiphys()?
+            .iter()
+            .find(|candidate| candidate.index == Some(wiphy))
+        {
+            Some(wiphy) => power.check(wiphy, frequency),
+            None => Ok(()),
+        }
+    }
+
+    /// Resolve the id of a nl80211 multicast group with the generic netlink controller
+    pub fn resolve_multicast_group(&self, group: MulticastGroup) -> Result<u32, Error> {
+        resolve_multicast_group(group)
+    }
+
+    /// Join nl80211 multicast groups to receive their notifications
+    ///
+    /// Notifications received while waiting for the reply of a command are kept until they are read
+    /// with [`recv_event`](#method.recv_event), a dedicated socket is advised to listen to busy groups.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nl80211::{MulticastGroup, Socket};
+    ///
+    /// # fn main() -> Result<(), nl80211::Error>{
+    ///   let mut socket = Socket::connect()?;
+    ///   socket.subscribe(&[MulticastGroup::Config, MulticastGroup::Mlme])?;
+    ///   for event in socket.events() {
+    ///       println!("{:?}", event?);
+    ///   }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn subscribe(&mut self, groups: &[MulticastGroup]) -> Result<(), Error> {
+        join(&self.sock, groups)
+    }
+
+    /// Wait for the next notification of the joined multicast groups
+    pub fn recv_event(&mut self) -> Result<Event, Error> {
+        let notification = self.recv_notification()?;
+        decode_event(notification)
+    }
+
+    /// Wait for the next notification message of the joined multicast groups
+    fn recv_notification(&mut self) -> Result<Genlmsghdr<Nl80211Cmd, Nl80211Attr>, Error> {
+        loop {
+            if let Some(notification) = self.notifications.pop_front() {
+                return Ok(notification);
+            }
+
+            // Replies left over by a previous command are dropped
+            if let Received::Notification(message) = self.recv_message()? {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// Wait for the next notification message until a deadline
+    ///
+    /// Returns `None` once the deadline is reached. The receive timeout of the socket is changed,
+    /// it is meant to be used on a socket dedicated to notifications.
+    fn recv_notification_before(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<Option<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, Error> {
+        loop {
+            if let Some(notification) = self.notifications.pop_front() {
+                return Ok(Some(notification));
+            }
+
+            // Messages already received don't need to wait for the socket
+            if self.messages.is_empty() {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(None);
+                }
+                set_recv_timeout(&self.sock, remaining)?;
+
+                match self.recv_datagram() {
+                    Ok(datagram) => self.messages.extend(split_datagram(datagram)?),
+                    // The receive timeout expired
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(NlError::from(err).into()),
+                }
+            }
+
+            // Replies left over by a previous command are dropped
+            if let Received::Notification(message) = self.recv_message()? {
+                return Ok(Some(message));
+            }
+        }
+    }
+
+    /// Iterate over the notifications of the joined multicast groups
+    pub fn events(&mut self) -> Events<'_> {
+        Events { socket: self }
+    }
+
+    /// Send a nl80211 command
+    fn send(
+        &mut self,
+        cmd: Nl80211Cmd,
+        flags: Vec<NlmF>,
+        attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+    ) -> Result<(), Error> {
+        let nlhdr = request(self.family_id, cmd, flags, attrs)?;
+        self.sock.send_nl(nlhdr)?;
+        Ok(())
+    }
+
+    /// Send a command to an interface and wait for the kernel to accept it
+    fn send_mlme(
+        &mut self,
+        cmd: Nl80211Cmd,
+        interface_attr_if_index: u32,
+        params: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+    ) -> Result<(), Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?];
+        attrs.extend(params);
+
+        self.send(cmd, vec![NlmF::Request, NlmF::Ack], attrs)?;
+        self.recv_ack()
+    }
+
+    /// Wait for the kernel to acknowledge the last command
+    fn recv_ack(&mut self) -> Result<(), Error> {
+        while self.recv()?.is_some() {}
+        Ok(())
+    }
+
+    /// Receive and parse every message of a dump until the kernel signals its end
+    fn recv_dump<T: FromNlAttributeHandle>(&mut self) -> Result<Vec<T>, Error> {
+        let mut results = Vec::new();
+
+        while let Some(response) = self.recv()? {
+            results.push(T::from_handle(response.get_attr_handle())?);
+        }
+
+        Ok(results)
+    }
+
+    /// Receive the next nl80211 message
+    ///
+    /// Returns `None` once the kernel ends the exchange (end of a dump or acknowledgment) and an
+    /// error when the kernel rejected the request.
+    fn recv(&mut self) -> Result<Option<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, Error> {
+        loop {
+            match self.recv_message()? {
+                Received::Reply(message) => return Ok(Some(message)),
+                Received::End => return Ok(None),
+                Received::Notification(message) => self.notifications.push_back(message),
+                Received::Noop => (),
+            }
+        }
+    }
+
+    /// Receive and decode a single netlink message
+    fn recv_message(&mut self) -> Result<Received, Error> {
+        let response = loop {
+            match self.messages.pop_front() {
+                Some(response) => break response,
+                None => {
+                    let datagram = self.recv_datagram().map_err(NlError::from)?;
+                    self.messages.extend(split_datagram(datagram)?);
+                }
+            }
+        };
+
+        decode(response)
+    }
+
+    /// Wait until the kernel sends a datagram and read it
+    ///
+    /// The error of the socket is kept as is, so that a receive timeout can be told apart.
+    fn recv_datagram(&mut self) -> Result<Vec<u8>, io::Error> {
+        let mut datagram = vec![0; MAX_NL_LENGTH];
+        match self.sock.recv(&mut datagram, 0)? {
+            0 => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "No data could be read from the socket",
+            )),
+            len => {
+                datagram.truncate(len);
+                Ok(datagram)
+            }
+        }
+    }
+}
+
+/// A netlink message received on a nl80211 socket
+pub(crate) enum Received {
+    /// A message answering a request
+    Reply(Genlmsghdr<Nl80211Cmd, Nl80211Attr>),
+    /// The end of a dump or the acknowledgment of a request
+    End,
+    /// A notification sent to a multicast group
+    Notification(Genlmsghdr<Nl80211Cmd, Nl80211Attr>),
+    /// A message carrying no information
+    Noop,
+}
+
+/// Open a generic netlink socket and resolve the nl80211 family id
+pub(crate) fn open() -> Result<(NlSocket, u16), Error> {
+    let family_id = NlSocket::new(NlFamily::Generic, true)
+        .map_err(NlError::from)?
+        .resolve_genl_family(NL_80211_GENL_NAME)?;
+
+    let track_seq = true;
+    let mut nl80211sock = NlSocket::new(NlFamily::Generic, track_seq).map_err(NlError::from)?;
+
+    let pid = None;
+    let groups = None;
+    nl80211sock.bind(pid, groups).map_err(NlError::from)?;
+
+    // Ask the kernel to explain its errors, kernels without extended ACK support ignore it
+    let enable: libc::c_int = 1;
+    unsafe {
+        libc::setsockopt(
+            nl80211sock.as_raw_fd(),
+            libc::SOL_NETLINK,
+            NETLINK_EXT_ACK,
+            &enable as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        );
+    }
+
+    Ok((nl80211sock, family_id))
+}
+
+/// Resolve the id of a nl80211 multicast group with the generic netlink controller
+pub(crate) fn resolve_multicast_group(group: MulticastGroup) -> Result<u32, Error> {
+    // Resolve on a dedicated socket so that pending notifications are left untouched
+    Ok(NlSocket::new(NlFamily::Generic, true)
+        .map_err(NlError::from)?
+        .resolve_nl_mcast_group(NL_80211_GENL_NAME, group.name())?)
+}
+
+/// Add a netlink socket to nl80211 multicast groups
+pub(crate) fn join(sock: &NlSocket, groups: &[MulticastGroup]) -> Result<(), Error> {
+    for group in groups {
+        let group_id = resolve_multicast_group(*group)?;
+
+        let result = unsafe {
+            libc::setsockopt(
+                sock.as_raw_fd(),
+                libc::SOL_NETLINK,
+                libc::NETLINK_ADD_MEMBERSHIP,
+                &group_id as *const _ as *const libc::c_void,
+                std::mem::size_of::<u32>() as libc::socklen_t,
+            )
+        };
+
+        if result != 0 {
+            return Err(NlError::from(std::io::Error::last_os_error()).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Add a message of a split wiphy dump to the wiphys received so far
+///
+/// Each wiphy is described by several consecutive messages.
+pub(crate) fn merge_wiphy(
+    wiphys: &mut Vec<Wiphy>,
+    handle: AttrHandle<Nl80211Attr>,
+) -> Result<(), Error> {
+    let index = handle
+        .get_attr_payload_as::<u32>(Nl80211Attr::AttrWiphy)
+        .ok();
+
+    match wiphys.last_mut() {
+        Some(wiphy) if wiphy.index == index => wiphy.update(handle),
+        _ => {
+            wiphys.push(Wiphy::from_handle(handle)?);
+            Ok(())
+        }
+    }
+}
+
+/// Bound the time a netlink socket waits for a message
+fn set_recv_timeout(sock: &NlSocket, timeout: Duration) -> Result<(), Error> {
+    // A null timeout would wait forever
+    let timeout = libc::timeval {
+        tv_sec: timeout.as_secs() as libc::time_t,
+        tv_usec: timeout.subsec_micros().max(1) as libc::suseconds_t,
+    };
+
+    let result = unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            &timeout as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+        )
+    };
+
+    if result != 0 {
+        return Err(NlError::from(std::io::Error::last_os_error()).into());
+    }
+
+    Ok(())
+}
+
+/// Error of a scan cancelled by the kernel
+pub(crate) fn scan_aborted() -> Error {
+    Error::Kernel(KernelError {
+        errno: libc::ECANCELED,
+        message: Some("Scan aborted".into()),
+        offset: None,
+    })
+}
+
+/// Error of a scan whose end wasn't reported in time
+pub(crate) fn scan_timed_out() -> Error {
+    Error::Kernel(KernelError {
+        errno: libc::ETIMEDOUT,
+        message: Some("Scan timed out".into()),
+        offset: None,
+    })
+}
+
+/// Error of a connection whose result wasn't reported in time
+pub(crate) fn connect_timed_out() -> Error {
+    Error::Kernel(KernelError {
+        errno: libc::ETIMEDOUT,
+        message: Some("Connection timed out".into()),
+        offset: None,
+    })
+}
+
+/// Error of a connection interrupted by a disconnection
+pub(crate) fn connect_aborted(reason: Option<u16>) -> Error {
+    let message = match reason {
+        Some(reason) => format!("Disconnected while connecting (reason {})", reason),
+        None => "Disconnected while connecting".to_string(),
+    };
+
+    Error::Kernel(KernelError {
+        errno: libc::ECONNABORTED,
+        message: Some(message),
+        offset: None,
+    })
+}
+
+/// Build the attributes of the nl80211 command CmdSetInterface
+pub(crate) fn set_interface_attrs(
+    interface_attr_if_index: u32,
+    iftype: Option<Nl80211Iftype>,
+    options: &InterfaceOptions,
+) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+    // The kernel ignores the MAC address of CmdSetInterface
+    if options.mac.is_some() {
+        return Err(Error::Invalid(
+            "The MAC address of an existing interface can't be changed".into(),
+        ));
+    }
+
+    let mut attrs = vec![Nlattr::new(
+        None,
+        Nl80211Attr::AttrIfindex,
+        interface_attr_if_index.to_le_bytes().to_vec(),
+    )?];
+    if let Some(iftype) = iftype {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrIftype,
+            u32::from(u16::from(iftype)).to_le_bytes().to_vec(),
+        )?);
+    }
+    attrs.extend(options.to_attrs()?);
+
+    Ok(attrs)
+}
+
+/// Explain why the kernel refused to change the type of an interface
+pub(crate) fn explain_interface_busy(err: Error) -> Error {
+    match err {
+        Error::Kernel(mut err) if err.errno == libc::EBUSY && err.message.is_none() => {
+            err.message = Some("The interface must be down to change its type".into());
+            Error::Kernel(err)
+        }
+        err => err,
+    }
+}
+
+/// Build the attributes selecting a key of an interface
+pub(crate) fn key_selector(
+    interface_attr_if_index: u32,
+    index: u8,
+    mac: Option<MacAddr>,
+) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+    let mut attrs = vec![
+        Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            interface_attr_if_index.to_le_bytes().to_vec(),
+        )?,
+        Nlattr::new(None, Nl80211Attr::AttrKeyIdx, vec![index])?,
+    ];
+
+    if let Some(mac) = mac {
+        attrs.push(Nlattr::new(
+            None,
+            Nl80211Attr::AttrMac,
+            mac.as_bytes().to_vec(),
+        )?);
+    }
+
+    Ok(attrs)
+}
+
+/// Build the netlink message of a nl80211 command
+pub(crate) fn request(
+    family_id: u16,
+    cmd: Nl80211Cmd,
+    flags: Vec<NlmF>,
+    attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+) -> Result<Nlmsghdr<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, Error> {
+    let genlhdr = Genlmsghdr::new(cmd, NL_80211_GENL_VERSION, attrs)?;
+    let nlhdr = {
+        let len = None;
+        let nl_type = family_id;
+        let seq = None;
+        let pid = None;
+        let payload = genlhdr;
+        Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
+    };
+
+    Ok(nlhdr)
+}
+
+/// Decode a netlink message received on a nl80211 socket
+///
+/// Fails when the message is an error sent back by the kernel.
+pub(crate) fn decode(response: Nlmsghdr<Nlmsg, Vec<u8>>) -> Result<Received, Error> {
+    match response.nl_type {
+        Nlmsg::Error => {
+            let flags = response
+                .nl_flags
+                .iter()
+                .fold(0, |acc, flag| acc | u16::from(flag));
+            let error = KernelError::from_payload(flags, &response.nl_payload)?;
+            match error.errno {
+                0 => Ok(Received::End),
+                _ => Err(error.into()),
+            }
+        }
+        Nlmsg::Done => Ok(Received::End),
+        Nlmsg::Noop => Ok(Received::Noop),
+        _ => {
+            let mut buffer = StreamReadBuffer::new(&response.nl_payload);
+            buffer.set_size_hint(response.nl_payload.len());
+            let message = Genlmsghdr::deserialize(&mut buffer)?;
+
+            // Notifications are the only messages not bound to a request sequence number
+            if response.nl_seq == 0 {
+                Ok(Received::Notification(message))
+            } else {
+                Ok(Received::Reply(message))
+            }
+        }
+    }
+}
+
+/// Split a datagram into the netlink messages it carries
+pub(crate) fn split_datagram(datagram: Vec<u8>) -> Result<Vec<Nlmsghdr<Nlmsg, Vec<u8>>>, Error> {
+    let mut buffer = StreamReadBuffer::new(datagram);
+    let mut messages = Vec::new();
+
+    while !buffer.at_end() {
+        messages.push(Nlmsghdr::deserialize(&mut buffer)?);
+    }
+
+    Ok(messages)
+}
+
+/// Decode a notification of a nl80211 multicast group
+pub(crate) fn decode_event(
+    notification: Genlmsghdr<Nl80211Cmd, Nl80211Attr>,
+) -> Result<Event, Error> {
+    let cmd = notification.cmd.clone();
+    Event::from_message(cmd, notification.get_attr_handle())
+}
+
+/// Blocking iterator over the notifications received by a [`Socket`](struct.Socket.html)
+pub struct Events<'a> {
+    socket: &'a mut Socket,
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Result<Event, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.socket.recv_event())
+    }
+}
+
+#[cfg(test)]
+mod test_socket {
+    use super::*;
+    use neli::StreamWriteBuffer;
+
+    const FAMILY_ID: u16 = 0x1c;
+
+    /// Create a netlink socket reading the datagrams written to the returned file descriptor
+    pub(super) fn nl_socket_pair() -> (NlSocket, libc::c_int) {
+        let sock = NlSocket::new(NlFamily::Generic, true).unwrap();
+
+        let mut fds = [0; 2];
+        unsafe {
+            assert_eq!(
+                libc::socketpair(libc::AF_UNIX, libc::SOCK_DGRAM, 0, fds.as_mut_ptr()),
+                0
+            );
+            assert!(libc::dup2(fds[0], sock.as_raw_fd()) >= 0);
+            libc::close(fds[0]);
+        }
+
+        (sock, fds[1])
+    }
+
+    fn socket_pair() -> (Socket, libc::c_int) {
+        let (sock, fd) = nl_socket_pair();
+        let socket = Socket {
+            sock,
+            family_id: FAMILY_ID,
+            notifications: VecDeque::new(),
+            messages: VecDeque::new(),
+        };
+        (socket, fd)
+    }
+
+    /// Write a nl80211 message, notifications have a null sequence number
+    pub(super) fn push_message(
+        fd: libc::c_int,
+        seq: u32,
+        cmd: Nl80211Cmd,
+        attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>>,
+    ) {
+        let genlhdr = Genlmsghdr::new(cmd, NL_80211_GENL_VERSION, attrs).unwrap();
+        let nlhdr = Nlmsghdr::new(None, FAMILY_ID, vec![], Some(seq), None, genlhdr);
+
+        let mut buffer = StreamWriteBuffer::new_growable(Some(nlhdr.asize()));
+        nlhdr.serialize(&mut buffer).unwrap();
+        let datagram: &[u8] = buffer.as_ref();
+        let sent = unsafe {
+            libc::send(
+                fd,
+                datagram.as_ptr() as *const libc::c_void,
+                datagram.len(),
+                0,
+            )
+        };
+        assert_eq!(sent, datagram.len() as isize);
+    }
+
+    pub(super) fn ifindex_attr(ifindex: u32) -> Nlattr<Nl80211Attr, Vec<u8>> {
+        Nlattr::new(
+            None,
+            Nl80211Attr::AttrIfindex,
+            ifindex.to_le_bytes().to_vec(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recv_event() {
+        let (mut socket, fd) = socket_pair();
+
+        // A reply left over by a previous command is dropped
+        push_message(fd, 1, Nl80211Cmd::CmdGetInterface, vec![ifindex_attr(2)]);
+        push_message(fd, 0, Nl80211Cmd::CmdNewScanResults, vec![ifindex_attr(3)]);
+        push_message(fd, 0, Nl80211Cmd::CmdScanAborted, vec![ifindex_attr(3)]);
+
+        assert_eq!(
+            socket.recv_event().unwrap(),
+            Event::NewScanResults { ifindex: Some(3) }
+        );
+        assert_eq!(
+            socket.events().next().unwrap().unwrap(),
+            Event::ScanAborted { ifindex: Some(3) }
+        );
+
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn test_recv_notification_before() {
+        let (mut socket, fd) = socket_pair();
+
+        push_message(fd, 0, Nl80211Cmd::CmdTriggerScan, vec![ifindex_attr(3)]);
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let notification = socket.recv_notification_before(deadline).unwrap().unwrap();
+        assert_eq!(notification.cmd, Nl80211Cmd::CmdTriggerScan);
+
+        // Errors other than the receive timeout are returned before the deadline
+        unsafe { libc::shutdown(socket.sock.as_raw_fd(), libc::SHUT_RD) };
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert!(socket.recv_notification_before(deadline).is_err());
+        assert!(Instant::now() < deadline);
+
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn test_split_datagram() {
+        let datagram = vec![
+            20, 0, 0, 0, 3, 0, 2, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let messages = split_datagram(datagram).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].nl_type, Nlmsg::Done);
+        assert_eq!(messages[0].nl_payload, vec![0, 0, 0, 0]);
+        assert_eq!(messages[1].nl_type, Nlmsg::Noop);
+    }
+
+    #[test]
+    fn test_wait_scan_done() {
+        let (mut socket, fd) = socket_pair();
+
+        // Notifications of other interfaces and the ones which can't be decoded are ignored
+        push_message(fd, 0, Nl80211Cmd::CmdTriggerScan, vec![ifindex_attr(3)]);
+        push_message(fd, 0, Nl80211Cmd::CmdNewScanResults, vec![ifindex_attr(2)]);
+        push_message(
+            fd,
+            0,
+            Nl80211Cmd::CmdConnect,
+            vec![
+                ifindex_attr(3),
+                Nlattr::new(None, Nl80211Attr::AttrMac, vec![0x02, 0x00]).unwrap(),
+            ],
+        );
+        push_message(fd, 0, Nl80211Cmd::CmdNewScanResults, vec![ifindex_attr(3)]);
+        socket.wait_scan_done(3).unwrap();
+
+        push_message(fd, 0, Nl80211Cmd::CmdScanAborted, vec![ifindex_attr(3)]);
+        assert!(matches!(
+            socket.wait_scan_done(3),
+            Err(Error::Kernel(KernelError {
+                errno: libc::ECANCELED,
+                ..
+            }))
+        ));
+
+        // Nothing is received before the deadline
+        let deadline = Instant::now() + Duration::from_millis(50);
+        assert!(socket.recv_notification_before(deadline).unwrap().is_none());
+        assert!(Instant::now() >= deadline);
+
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn test_wait_connect_result() {
+        let (mut socket, fd) = socket_pair();
+        let bssid = MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00]);
+
+        // The result of another interface is ignored, even when it can't be decoded
+        push_message(fd, 0, Nl80211Cmd::CmdConnect, vec![ifindex_attr(2)]);
+        push_message(
+            fd,
+            0,
+            Nl80211Cmd::CmdConnect,
+            vec![
+                ifindex_attr(2),
+                Nlattr::new(None, Nl80211Attr::AttrMac, vec![0x02, 0x00]).unwrap(),
+            ],
+        );
+        push_message(
+            fd,
+            0,
+            Nl80211Cmd::CmdConnect,
+            vec![
+                ifindex_attr(3),
+                Nlattr::new(None, Nl80211Attr::AttrMac, bssid.as_bytes().to_vec()).unwrap(),
+                Nlattr::new(
+                    None,
+                    Nl80211Attr::AttrStatusCode,
+                    0u16.to_le_bytes().to_vec(),
+                )
+                .unwrap(),
+            ],
+        );
+
+        let start = Instant::now();
+        let result = socket.wait_connect_result(3).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(result.bssid, Some(bssid));
+        assert!(result.is_success());
+
+        push_message(
+            fd,
+            0,
+            Nl80211Cmd::CmdDisconnect,
+            vec![
+                ifindex_attr(3),
+                Nlattr::new(
+                    None,
+                    Nl80211Attr::AttrReasonCode,
+                    3u16.to_le_bytes().to_vec(),
+                )
+                .unwrap(),
+            ],
+        );
+        assert!(matches!(
+            socket.wait_connect_result(3),
+            Err(Error::Kernel(KernelError {
+                errno: libc::ECONNABORTED,
+                ..
+            }))
+        ));
+
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn test_set_interface_attrs() {
+        let options = InterfaceOptions {
+            four_address: Some(true),
+            ..Default::default()
+        };
+        let attrs = set_interface_attrs(3, None, &options).unwrap();
+        assert_eq!(attrs.len(), 2);
+        assert_eq!(attrs[1].nla_type, Nl80211Attr::Attr4addr);
+
+        let options = InterfaceOptions {
+            mac: Some(MacAddr::from([0x02, 0x00, 0x00, 0x00, 0x01, 0x00])),
+            ..Default::default()
+        };
+        assert!(matches!(
+            set_interface_attrs(3, None, &options),
+            Err(Error::Invalid(_))
+        ));
+    }
 }
@@ -1,13 +1,18 @@
-use crate::attr::{Nl80211Attr, Nl80211RateInfo, Nl80211StaInfo};
+use crate::attr::{
+    Nl80211Ac, Nl80211Attr, Nl80211MeshPowerMode, Nl80211PlinkState, Nl80211StaBssParam,
+    Nl80211StaFlags, Nl80211StaInfo, Nl80211StaWmeAttr, Nl80211TidStats, NlaNested,
+};
+use crate::error::Error;
 use crate::helpers::parse_macaddr;
 use crate::nl80211traits::*;
+use crate::rate::RateInfo;
 use byteorder::{LittleEndian, ReadBytesExt};
 use macaddr::MacAddr;
-use neli::err::NlError;
-use neli::nlattr::AttrHandle;
+use neli::nlattr::{AttrHandle, Nlattr};
+use std::collections::BTreeMap;
 use std::fmt;
 
-/// A struct representing a remote station (Access Point)
+/// A struct representing a remote station (Access Point or associated client)
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Station {
     /// Signal strength average
@@ -18,25 +23,194 @@ pub struct Station {
     pub bssid: Option<MacAddr>,
     /// Time since the station is last connected in seconds
     pub connected_time: Option<u32>,
-    /// Reception bitrate (u32)
-    pub rx_bitrate: Option<u32>,
+    /// Rate of the last frame received from this station
+    pub rx_bitrate: Option<RateInfo>,
     /// Total received packets (MSDUs and MMPDUs) from this station
     pub rx_packets: Option<u32>,
     /// Signal strength of last received PPDU
     pub signal: Option<i8>,
-    /// Transmission bitrate
-    pub tx_bitrate: Option<u32>,
+    /// Rate of the last frame transmitted to this station
+    pub tx_bitrate: Option<RateInfo>,
     /// Total failed packets (MPDUs) to this station
     pub tx_failed: Option<u32>,
     /// Total transmitted packets (MSDUs and MMPDUs) to this station
     pub tx_packets: Option<u32>,
     /// Total retries (MPDUs) to this station
     pub tx_retries: Option<u32>,
+    /// Time since the last activity of the station in milliseconds
+    pub inactive_time: Option<u32>,
+    /// Total received bytes (MPDU length) from this station
+    pub rx_bytes: Option<u64>,
+    /// Total transmitted bytes (MPDU length) to this station
+    pub tx_bytes: Option<u64>,
+    /// Signal strength of last received PPDU for each antenna chain
+    pub chain_signal: Vec<i8>,
+    /// Signal strength average for each antenna chain
+    pub chain_signal_avg: Vec<i8>,
+    /// Expected throughput in kb/s, as estimated by the rate control algorithm
+    pub expected_throughput: Option<u32>,
+    /// Count of packets from this station dropped for unspecified reasons
+    pub rx_drop_misc: Option<u64>,
+    /// Count of beacons received from this station
+    pub beacon_rx: Option<u64>,
+    /// Signal strength average of the beacons received from this station
+    pub beacon_signal_avg: Option<i8>,
+    /// Aggregate PPDU duration of the frames received from this station in microseconds
+    pub rx_duration: Option<u64>,
+    /// Timing offset with this station in microseconds
+    pub t_offset: Option<i64>,
+    /// Mesh local link ID
+    pub llid: Option<u16>,
+    /// Mesh peer link ID
+    pub plid: Option<u16>,
+    /// Mesh peer link state
+    pub plink_state: Option<Nl80211PlinkState>,
+    /// Mesh power mode for the link with this station
+    pub local_pm: Option<Nl80211MeshPowerMode>,
+    /// Mesh power mode of this station for the link
+    pub peer_pm: Option<Nl80211MeshPowerMode>,
+    /// Mesh power mode of this station for non-peer stations
+    pub nonpeer_pm: Option<Nl80211MeshPowerMode>,
+    /// Flags set on the station (authorized, WME, MFP...)
+    pub flags: Vec<Nl80211StaFlags>,
+    /// Parameters of the BSS, when this station is the access point
+    pub bss_param: Option<BssParam>,
+    /// Traffic statistics per TID, the TID 16 counts the non-QoS frames
+    pub tid_stats: BTreeMap<u8, TidStats>,
+}
+
+/// BSS parameters reported for an access point station
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BssParam {
+    /// CTS protection is enabled
+    pub cts_protection: bool,
+    /// Short preamble is enabled
+    pub short_preamble: bool,
+    /// Short slot time is enabled
+    pub short_slot_time: bool,
+    /// DTIM period of the beacons
+    pub dtim_period: Option<u8>,
+    /// Beacon interval in time units (1024 µs)
+    pub beacon_interval: Option<u16>,
+}
+
+impl BssParam {
+    /// Parse the nested attribute StaInfoBssParam
+    fn from_handle(handle: AttrHandle<Nl80211StaBssParam>) -> Result<BssParam, Error> {
+        let mut bss_param = BssParam::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211StaBssParam::StaBssParamCtsProt => bss_param.cts_protection = true,
+                Nl80211StaBssParam::StaBssParamShortPreamble => bss_param.short_preamble = true,
+                Nl80211StaBssParam::StaBssParamShortSlotTime => bss_param.short_slot_time = true,
+                Nl80211StaBssParam::StaBssParamDtimPeriod => {
+                    bss_param.dtim_period = Some(payload.read_u8()?)
+                }
+                Nl80211StaBssParam::StaBssParamBeaconInterval => {
+                    bss_param.beacon_interval = Some(payload.read_u16::<LittleEndian>()?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(bss_param)
+    }
+}
+
+/// Traffic statistics of a station for a single TID
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TidStats {
+    /// Number of MSDUs received
+    pub rx_msdu: Option<u64>,
+    /// Number of MSDUs transmitted or attempted to be transmitted
+    pub tx_msdu: Option<u64>,
+    /// Number of retries of transmitted MSDUs, without the first attempts
+    pub tx_msdu_retries: Option<u64>,
+    /// Number of MSDUs which failed to be transmitted
+    pub tx_msdu_failed: Option<u64>,
+}
+
+impl TidStats {
+    /// Parse the statistics of a TID nested in the attribute StaInfoTidStats
+    fn from_handle(handle: AttrHandle<Nl80211TidStats>) -> Result<TidStats, Error> {
+        let mut stats = TidStats::default();
+
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211TidStats::TidStatsRxMsdu => {
+                    stats.rx_msdu = Some(payload.read_u64::<LittleEndian>()?)
+                }
+                Nl80211TidStats::TidStatsTxMsdu => {
+                    stats.tx_msdu = Some(payload.read_u64::<LittleEndian>()?)
+                }
+                Nl80211TidStats::TidStatsTxMsduRetries => {
+                    stats.tx_msdu_retries = Some(payload.read_u64::<LittleEndian>()?)
+                }
+                Nl80211TidStats::TidStatsTxMsduFailed => {
+                    stats.tx_msdu_failed = Some(payload.read_u64::<LittleEndian>()?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Parse the nested attribute StaInfoTidStats, whose attribute types are the TIDs plus one
+fn parse_tid_stats(handle: AttrHandle<NlaNested>) -> Result<BTreeMap<u8, TidStats>, Error> {
+    let mut tid_stats = BTreeMap::new();
+    for attr in handle.iter() {
+        let tid = match u16::from(&attr.nla_type).checked_sub(1) {
+            Some(tid) => tid as u8,
+            None => continue,
+        };
+        tid_stats.insert(tid, TidStats::from_handle(attr.get_nested_attributes()?)?);
+    }
+    Ok(tid_stats)
+}
+
+/// Access category of the frames sent with a TID, following the 802.1D user priorities
+///
+/// The TIDs 8 to 15 use the access category of their lower three bits, and the non-QoS TID 16 is
+/// sent as best effort.
+pub fn tid_to_ac(tid: u8) -> Nl80211Ac {
+    match tid & 0x07 {
+        1 | 2 => Nl80211Ac::AcBk,
+        4 | 5 => Nl80211Ac::AcVi,
+        6 | 7 => Nl80211Ac::AcVo,
+        _ => Nl80211Ac::AcBe,
+    }
+}
+
+/// Parse the per chain signal strengths of the nested attributes StaInfoChainSignal and
+/// StaInfoChainSignalAvg
+fn parse_chain_signal(handle: AttrHandle<NlaNested>) -> Result<Vec<i8>, Error> {
+    let mut signals = Vec::new();
+    for attr in handle.iter() {
+        signals.push((&attr.payload[..]).read_i8()?);
+    }
+    Ok(signals)
+}
+
+/// Parse the struct nl80211_sta_flag_update of the attribute StaInfoStaFlags, keeping the flags
+/// set among the reported ones
+fn parse_sta_flags(mut payload: &[u8]) -> Result<Vec<Nl80211StaFlags>, Error> {
+    let mask = payload.read_u32::<LittleEndian>()?;
+    let set = payload.read_u32::<LittleEndian>()?;
+
+    Ok((1..u16::from(Nl80211StaFlags::StaFlagAfterLast))
+        .filter(|flag| mask & set & (1 << flag) != 0)
+        .map(Nl80211StaFlags::from)
+        .collect())
 }
 
 impl FromNlAttributeHandle for Station {
     /// Parse netlink messages returned by the nl80211 command CmdGetStation
-    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Station, NlError> {
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Station, Error> {
         let mut station = Station {
             ..Default::default()
         };
@@ -73,31 +247,94 @@ impl FromNlAttributeHandle for Station {
                             Nl80211StaInfo::StaInfoTxFailed => {
                                 station.tx_failed = Some(payload.read_u32::<LittleEndian>()?)
                             }
+                            Nl80211StaInfo::StaInfoInactiveTime => {
+                                station.inactive_time = Some(payload.read_u32::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoRxBytes => {
+                                let rx_bytes = payload.read_u32::<LittleEndian>()?;
+                                // The 64-bit counter is preferred when both are sent
+                                station.rx_bytes.get_or_insert(u64::from(rx_bytes));
+                            }
+                            Nl80211StaInfo::StaInfoTxBytes => {
+                                let tx_bytes = payload.read_u32::<LittleEndian>()?;
+                                station.tx_bytes.get_or_insert(u64::from(tx_bytes));
+                            }
+                            Nl80211StaInfo::StaInfoRxBytes64 => {
+                                station.rx_bytes = Some(payload.read_u64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoTxBytes64 => {
+                                station.tx_bytes = Some(payload.read_u64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoChainSignal => {
+                                station.chain_signal =
+                                    parse_chain_signal(sub_attr.get_nested_attributes()?)?
+                            }
+                            Nl80211StaInfo::StaInfoChainSignalAvg => {
+                                station.chain_signal_avg =
+                                    parse_chain_signal(sub_attr.get_nested_attributes()?)?
+                            }
+                            Nl80211StaInfo::StaInfoExpectedThroughput => {
+                                station.expected_throughput =
+                                    Some(payload.read_u32::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoRxDropMisc => {
+                                station.rx_drop_misc = Some(payload.read_u64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoBeaconRx => {
+                                station.beacon_rx = Some(payload.read_u64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoBeaconSignalAvg => {
+                                station.beacon_signal_avg = Some(payload.read_i8()?)
+                            }
+                            Nl80211StaInfo::StaInfoRxDuration => {
+                                station.rx_duration = Some(payload.read_u64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoTOffset => {
+                                station.t_offset = Some(payload.read_i64::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoLlid => {
+                                station.llid = Some(payload.read_u16::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoPlid => {
+                                station.plid = Some(payload.read_u16::<LittleEndian>()?)
+                            }
+                            Nl80211StaInfo::StaInfoPlinkState => {
+                                station.plink_state =
+                                    Some(Nl80211PlinkState::from(u16::from(payload.read_u8()?)))
+                            }
+                            Nl80211StaInfo::StaInfoLocalPm => {
+                                station.local_pm = Some(Nl80211MeshPowerMode::from(
+                                    payload.read_u32::<LittleEndian>()? as u16,
+                                ))
+                            }
+                            Nl80211StaInfo::StaInfoPeerPm => {
+                                station.peer_pm = Some(Nl80211MeshPowerMode::from(
+                                    payload.read_u32::<LittleEndian>()? as u16,
+                                ))
+                            }
+                            Nl80211StaInfo::StaInfoNonpeerPm => {
+                                station.nonpeer_pm = Some(Nl80211MeshPowerMode::from(
+                                    payload.read_u32::<LittleEndian>()? as u16,
+                                ))
+                            }
+                            Nl80211StaInfo::StaInfoStaFlags => {
+                                station.flags = parse_sta_flags(payload)?
+                            }
+                            Nl80211StaInfo::StaInfoBssParam => {
+                                station.bss_param =
+                                    Some(BssParam::from_handle(sub_attr.get_nested_attributes()?)?)
+                            }
+                            Nl80211StaInfo::StaInfoTidStats => {
+                                station.tid_stats =
+                                    parse_tid_stats(sub_attr.get_nested_attributes()?)?
+                            }
                             Nl80211StaInfo::StaInfoRxBitrate => {
-                                let bit_rate_handle =
-                                    sub_attr.get_nested_attributes::<Nl80211RateInfo>()?;
-                                for sub_sub_attr in bit_rate_handle.iter() {
-                                    if sub_sub_attr.nla_type != Nl80211RateInfo::RateInfoBitrate32 {
-                                        continue;
-                                    }
-                                    station.rx_bitrate = Some(
-                                        (&sub_sub_attr.payload[..]).read_u32::<LittleEndian>()?,
-                                    );
-                                    break;
-                                }
+                                station.rx_bitrate =
+                                    Some(RateInfo::from_handle(sub_attr.get_nested_attributes()?)?)
                             }
                             Nl80211StaInfo::StaInfoTxBitrate => {
-                                let bit_rate_handle =
-                                    sub_attr.get_nested_attributes::<Nl80211RateInfo>()?;
-                                for sub_sub_attr in bit_rate_handle.iter() {
-                                    if sub_sub_attr.nla_type != Nl80211RateInfo::RateInfoBitrate32 {
-                                        continue;
-                                    }
-                                    station.tx_bitrate = Some(
-                                        (&sub_sub_attr.payload[..]).read_u32::<LittleEndian>()?,
-                                    );
-                                    break;
-                                }
+                                station.tx_bitrate =
+                                    Some(RateInfo::from_handle(sub_attr.get_nested_attributes()?)?)
                             }
                             _ => (),
                         }
@@ -146,19 +383,11 @@ impl fmt::Display for Station {
         };
 
         if let Some(bitrate) = &self.rx_bitrate {
-            result.push(format!(
-                "rx bitrate : {}.{} Mb/s",
-                bitrate / 10,
-                bitrate % 10
-            ))
+            result.push(format!("rx bitrate : {}", bitrate))
         };
 
         if let Some(bitrate) = &self.tx_bitrate {
-            result.push(format!(
-                "tx bitrate : {}.{} Mb/s",
-                bitrate / 10,
-                bitrate % 10
-            ))
+            result.push(format!("tx bitrate : {}", bitrate))
         }
 
         if let Some(tx_retries) = &self.tx_retries {
@@ -169,15 +398,191 @@ impl fmt::Display for Station {
             result.push(format!("tx failed : {}", tx_failed))
         }
 
+        if let Some(inactive_time) = &self.inactive_time {
+            result.push(format!("inactive time : {} ms", inactive_time))
+        }
+
+        if let Some(rx_bytes) = &self.rx_bytes {
+            result.push(format!("rx bytes : {}", rx_bytes))
+        }
+
+        if let Some(tx_bytes) = &self.tx_bytes {
+            result.push(format!("tx bytes : {}", tx_bytes))
+        }
+
+        if !self.chain_signal.is_empty() {
+            result.push(format!("chain signal : {:?} dBm", self.chain_signal))
+        }
+
+        if !self.chain_signal_avg.is_empty() {
+            result.push(format!(
+                "chain average signal : {:?} dBm",
+                self.chain_signal_avg
+            ))
+        }
+
+        if let Some(throughput) = &self.expected_throughput {
+            result.push(format!(
+                "expected throughput : {}.{} Mb/s",
+                throughput / 1000,
+                throughput % 1000 / 100
+            ))
+        }
+
+        if let Some(beacon_rx) = &self.beacon_rx {
+            result.push(format!("beacon rx : {}", beacon_rx))
+        }
+
+        if let Some(beacon_signal_avg) = &self.beacon_signal_avg {
+            result.push(format!("beacon average signal : {} dBm", beacon_signal_avg))
+        }
+
+        if let Some(rx_drop_misc) = &self.rx_drop_misc {
+            result.push(format!("rx drop misc : {}", rx_drop_misc))
+        }
+
+        if let Some(rx_duration) = &self.rx_duration {
+            result.push(format!("rx duration : {} us", rx_duration))
+        }
+
+        if let Some(bss_param) = &self.bss_param {
+            if let Some(dtim_period) = &bss_param.dtim_period {
+                result.push(format!("dtim period : {}", dtim_period))
+            }
+
+            if let Some(beacon_interval) = &bss_param.beacon_interval {
+                result.push(format!("beacon interval : {}", beacon_interval))
+            }
+        }
+
         write!(f, "{}", result.join("\n"))
     }
 }
 
+/// Parameters of a station added or changed by an access point with the nl80211 commands
+/// CmdNewStation and CmdSetStation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StationParams {
+    /// Association ID of the station
+    pub aid: Option<u16>,
+    /// Listen interval of the station, in beacon intervals
+    pub listen_interval: Option<u16>,
+    /// Rates supported by the station in units of 500 kb/s
+    pub supported_rates: Option<Vec<u8>>,
+    /// Capability information of the station
+    pub capability: Option<u16>,
+    /// HT Capabilities element of the station (without element ID and length)
+    pub ht_capabilities: Option<Vec<u8>>,
+    /// VHT Capabilities element of the station (without element ID and length)
+    pub vht_capabilities: Option<Vec<u8>>,
+    /// Flags to set (authorized, WME, MFP, TDLS peer...)
+    pub set_flags: Vec<Nl80211StaFlags>,
+    /// Flags to clear
+    pub clear_flags: Vec<Nl80211StaFlags>,
+    /// U-APSD enabled access categories (bitmap of VO, VI, BK and BE), WME stations only
+    pub uapsd_queues: Option<u8>,
+    /// Maximum service period, WME stations only
+    pub max_sp: Option<u8>,
+}
+
+impl ToNlAttributes for StationParams {
+    /// Build the attributes of the nl80211 commands CmdNewStation and CmdSetStation
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = Vec::new();
+
+        if let Some(aid) = self.aid {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrStaAid,
+                aid.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(listen_interval) = self.listen_interval {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrStaListenInterval,
+                listen_interval.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(rates) = &self.supported_rates {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrStaSupportedRates,
+                rates.clone(),
+            )?);
+        }
+
+        if let Some(capability) = self.capability {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrStaCapability,
+                capability.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        if let Some(ht_capabilities) = &self.ht_capabilities {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrHtCapability,
+                ht_capabilities.clone(),
+            )?);
+        }
+
+        if let Some(vht_capabilities) = &self.vht_capabilities {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrVhtCapability,
+                vht_capabilities.clone(),
+            )?);
+        }
+
+        if !self.set_flags.is_empty() || !self.clear_flags.is_empty() {
+            let set = flags_bitmap(&self.set_flags);
+            let mask = set | flags_bitmap(&self.clear_flags);
+            let mut payload = mask.to_le_bytes().to_vec();
+            payload.extend(&set.to_le_bytes());
+            attrs.push(Nlattr::new(None, Nl80211Attr::AttrStaFlags2, payload)?);
+        }
+
+        if self.uapsd_queues.is_some() || self.max_sp.is_some() {
+            let mut wme = Nlattr::new(None, Nl80211Attr::AttrStaWme, Vec::<u8>::new())?;
+            if let Some(uapsd_queues) = self.uapsd_queues {
+                wme.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211StaWmeAttr::StaWmeUapsdQueues,
+                    vec![uapsd_queues],
+                )?)?;
+            }
+            if let Some(max_sp) = self.max_sp {
+                wme.add_nested_attribute(&Nlattr::new(
+                    None,
+                    Nl80211StaWmeAttr::StaWmeMaxSp,
+                    vec![max_sp],
+                )?)?;
+            }
+            attrs.push(wme);
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Convert station flags to the bitmap of the nl80211 attribute AttrStaFlags2
+fn flags_bitmap(flags: &[Nl80211StaFlags]) -> u32 {
+    flags
+        .iter()
+        .fold(0, |acc, flag| acc | 1 << u32::from(u16::from(flag)))
+}
+
 #[cfg(test)]
 mod tests_station {
     use super::*;
     use crate::attr::Nl80211Attr::AttrMac;
     use crate::attr::Nl80211Attr::AttrStaInfo;
+    use crate::attr::Nl80211Attr::{AttrStaAid, AttrStaFlags2, AttrStaListenInterval, AttrStaWme};
+    use crate::attr::Nl80211ChanWidth;
     use neli::nlattr::Nlattr;
 
     #[test]
@@ -187,13 +592,27 @@ mod tests_station {
             beacon_loss: Some(0),
             bssid: Some(MacAddr::from([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])),
             connected_time: Some(5494),
-            rx_bitrate: Some(6500),
+            rx_bitrate: Some(RateInfo {
+                bitrate: Some(6500),
+                vht_mcs: Some(7),
+                vht_nss: Some(2),
+                width: Some(Nl80211ChanWidth::ChanWidth80),
+                ..Default::default()
+            }),
             rx_packets: Some(425580),
             signal: Some(-61),
-            tx_bitrate: Some(8667),
+            tx_bitrate: Some(RateInfo {
+                bitrate: Some(8667),
+                vht_mcs: Some(9),
+                vht_nss: Some(2),
+                width: Some(Nl80211ChanWidth::ChanWidth80),
+                short_gi: true,
+                ..Default::default()
+            }),
             tx_failed: Some(45),
             tx_packets: Some(153870),
             tx_retries: Some(28425),
+            ..Default::default()
         };
 
         let expected_output = r#"bssid : FF:FF:FF:FF:FF:FF
@@ -203,8 +622,8 @@ mod tests_station {
         average signal : -59 dBm
         rx packets : 425580
         tx packets : 153870
-        rx bitrate : 650.0 Mb/s
-        tx bitrate : 866.7 Mb/s
+        rx bitrate : 650.0 MBit/s VHT-MCS 7 80MHz VHT-NSS 2
+        tx bitrate : 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
         tx retries : 28425
         tx failed : 45"#;
 
@@ -332,15 +751,123 @@ mod tests_station {
             beacon_loss: Some(0),
             bssid: Some(MacAddr::from([46, 46, 46, 46, 46, 46])),
             connected_time: Some(6929),
-            rx_bitrate: Some(390),
+            rx_bitrate: Some(RateInfo {
+                bitrate: Some(390),
+                mcs: Some(4),
+                ..Default::default()
+            }),
             rx_packets: Some(491746),
             signal: Some(-38),
-            tx_bitrate: Some(1040),
+            tx_bitrate: Some(RateInfo {
+                bitrate: Some(1040),
+                mcs: Some(13),
+                ..Default::default()
+            }),
             tx_failed: Some(47),
             tx_packets: Some(174601),
             tx_retries: Some(33307),
+            inactive_time: Some(760),
+            rx_bytes: Some(496788011),
+            tx_bytes: Some(23952227),
+            chain_signal: vec![-40, -43],
+            chain_signal_avg: vec![-44, -45],
+            expected_throughput: Some(41156),
+            rx_drop_misc: Some(951),
+            beacon_rx: Some(65249),
+            beacon_signal_avg: Some(-40),
+            flags: vec![
+                Nl80211StaFlags::StaFlagAuthorized,
+                Nl80211StaFlags::StaFlagWme,
+                Nl80211StaFlags::StaFlagAuthenticated,
+                Nl80211StaFlags::StaFlagAssociated,
+            ],
+            bss_param: Some(BssParam {
+                cts_protection: false,
+                short_preamble: true,
+                short_slot_time: true,
+                dtim_period: Some(1),
+                beacon_interval: Some(100),
+            }),
+            tid_stats: (0..=16)
+                .map(|tid| {
+                    let (rx_msdu, tx_msdu) = match tid {
+                        0 => (354216, 174407),
+                        6 => (0, 180),
+                        7 => (0, 2),
+                        16 => (6509, 4),
+                        _ => (0, 0),
+                    };
+                    let stats = TidStats {
+                        rx_msdu: Some(rx_msdu),
+                        tx_msdu: Some(tx_msdu),
+                        tx_msdu_retries: Some(0),
+                        tx_msdu_failed: Some(0),
+                    };
+                    (tid, stats)
+                })
+                .collect(),
+            ..Default::default()
         };
 
         assert_eq!(station, expected_station)
     }
+
+    #[test]
+    fn test_tid_to_ac() {
+        let acs: Vec<Nl80211Ac> = (0..8).map(tid_to_ac).collect();
+
+        assert_eq!(
+            acs,
+            vec![
+                Nl80211Ac::AcBe,
+                Nl80211Ac::AcBk,
+                Nl80211Ac::AcBk,
+                Nl80211Ac::AcBe,
+                Nl80211Ac::AcVi,
+                Nl80211Ac::AcVi,
+                Nl80211Ac::AcVo,
+                Nl80211Ac::AcVo,
+            ]
+        );
+        assert_eq!(tid_to_ac(16), Nl80211Ac::AcBe)
+    }
+
+    #[test]
+    fn test_params_to_attrs() {
+        let params = StationParams {
+            aid: Some(1),
+            listen_interval: Some(10),
+            set_flags: vec![Nl80211StaFlags::StaFlagAuthorized],
+            clear_flags: vec![Nl80211StaFlags::StaFlagMfp],
+            uapsd_queues: Some(0x0F),
+            ..Default::default()
+        };
+
+        let attrs = params.to_attrs().unwrap();
+
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 6,
+                nla_type: AttrStaAid,
+                payload: vec![1, 0],
+            },
+            Nlattr {
+                nla_len: 6,
+                nla_type: AttrStaListenInterval,
+                payload: vec![10, 0],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrStaFlags2,
+                payload: vec![18, 0, 0, 0, 2, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrStaWme,
+                payload: vec![5, 0, 1, 0, 15, 0, 0, 0],
+            },
+        ];
+
+        assert_eq!(attrs, expected_attrs)
+    }
 }
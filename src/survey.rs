@@ -0,0 +1,204 @@
+use crate::attr::{Nl80211Attr, Nl80211SurveyInfo};
+use crate::error::Error;
+use crate::nl80211traits::FromNlAttributeHandle;
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::nlattr::AttrHandle;
+use std::fmt;
+
+/// Survey of a channel returned by the nl80211 command CmdGetSurvey
+///
+/// Times are counted in milliseconds since the interface is up. A survey without frequency holds
+/// the statistics of the whole radio.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Survey {
+    /// Center frequency of the channel in MHz
+    pub frequency: Option<u32>,
+    /// Noise level of the channel in dBm
+    pub noise: Option<i8>,
+    /// The channel is currently used by the interface
+    pub in_use: bool,
+    /// Time the radio was turned on
+    pub time: Option<u64>,
+    /// Time the primary channel was sensed busy
+    pub time_busy: Option<u64>,
+    /// Time the extension channel was sensed busy
+    pub time_ext_busy: Option<u64>,
+    /// Time the radio spent receiving
+    pub time_rx: Option<u64>,
+    /// Time the radio spent transmitting
+    pub time_tx: Option<u64>,
+    /// Time the radio spent scanning
+    pub time_scan: Option<u64>,
+    /// Time the radio spent receiving frames of the local BSS
+    pub time_bss_rx: Option<u64>,
+}
+
+impl Survey {
+    /// Whether this survey holds the statistics of the whole radio rather than of a channel
+    pub fn is_radio_stats(&self) -> bool {
+        self.frequency.is_none()
+    }
+
+    /// Percentage of the active time the channel was sensed busy
+    pub fn utilization(&self) -> Option<f32> {
+        match (self.time, self.time_busy) {
+            (Some(time), Some(time_busy)) if time > 0 => {
+                Some(time_busy as f32 * 100.0 / time as f32)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromNlAttributeHandle for Survey {
+    /// Parse netlink messages returned by the nl80211 command CmdGetSurvey
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Survey, Error> {
+        let mut survey = Survey::default();
+
+        for attr in handle.iter() {
+            if attr.nla_type != Nl80211Attr::AttrSurveyInfo {
+                continue;
+            }
+
+            for sub_attr in attr.get_nested_attributes::<Nl80211SurveyInfo>()?.iter() {
+                let mut payload = &sub_attr.payload[..];
+                match sub_attr.nla_type {
+                    Nl80211SurveyInfo::SurveyInfoFrequency => {
+                        survey.frequency = Some(payload.read_u32::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoNoise => survey.noise = Some(payload.read_i8()?),
+                    Nl80211SurveyInfo::SurveyInfoInUse => survey.in_use = true,
+                    Nl80211SurveyInfo::SurveyInfoTime => {
+                        survey.time = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeBusy => {
+                        survey.time_busy = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeExtBusy => {
+                        survey.time_ext_busy = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeRx => {
+                        survey.time_rx = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeTx => {
+                        survey.time_tx = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeScan => {
+                        survey.time_scan = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeBssRx => {
+                        survey.time_bss_rx = Some(payload.read_u64::<LittleEndian>()?)
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(survey)
+    }
+}
+
+impl fmt::Display for Survey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Vec::new();
+
+        match self.frequency {
+            Some(frequency) if self.in_use => {
+                result.push(format!("frequency : {} MHz [in use]", frequency))
+            }
+            Some(frequency) => result.push(format!("frequency : {} MHz", frequency)),
+            None => result.push("frequency : whole radio".to_string()),
+        }
+
+        if let Some(noise) = &self.noise {
+            result.push(format!("noise : {} dBm", noise))
+        }
+
+        let times = [
+            ("channel active time", self.time),
+            ("channel busy time", self.time_busy),
+            ("extension channel busy time", self.time_ext_busy),
+            ("channel receive time", self.time_rx),
+            ("channel transmit time", self.time_tx),
+            ("channel scan time", self.time_scan),
+            ("channel BSS receive time", self.time_bss_rx),
+        ];
+        for (name, time) in times.iter() {
+            if let Some(time) = time {
+                result.push(format!("{} : {} ms", name, time))
+            }
+        }
+
+        if let Some(utilization) = self.utilization() {
+            result.push(format!("channel utilization : {:.1} %", utilization))
+        }
+
+        write!(f, "{}", result.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test_survey {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::nlattr::Nlattr;
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrIfindex,
+                payload: vec![3, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 48,
+                nla_type: AttrSurveyInfo,
+                payload: vec![
+                    8, 0, 1, 0, 108, 9, 0, 0, 5, 0, 2, 0, 164, 0, 0, 0, 4, 0, 3, 0, 12, 0, 4, 0,
+                    196, 9, 0, 0, 0, 0, 0, 0, 12, 0, 5, 0, 244, 1, 0, 0, 0, 0, 0, 0,
+                ],
+            },
+        ];
+
+        let survey = Survey::from_handle(AttrHandle::Owned(handler)).unwrap();
+
+        let expected_survey = Survey {
+            frequency: Some(2412),
+            noise: Some(-92),
+            in_use: true,
+            time: Some(2500),
+            time_busy: Some(500),
+            ..Default::default()
+        };
+
+        assert_eq!(survey, expected_survey);
+        assert_eq!(survey.utilization(), Some(20.0));
+        assert!(!survey.is_radio_stats());
+    }
+
+    #[test]
+    fn test_pretty_format() {
+        let survey = Survey {
+            frequency: Some(5180),
+            noise: Some(-95),
+            in_use: false,
+            time: Some(400),
+            time_busy: Some(30),
+            time_rx: Some(12),
+            ..Default::default()
+        };
+
+        let expected_output = r#"frequency : 5180 MHz
+        noise : -95 dBm
+        channel active time : 400 ms
+        channel busy time : 30 ms
+        channel receive time : 12 ms
+        channel utilization : 7.5 %"#;
+
+        assert_eq!(
+            format!("{}", survey),
+            expected_output.replace("\n        ", "\n")
+        )
+    }
+}
@@ -0,0 +1,144 @@
+use crate::attr::{Nl80211Attr, Nl80211TxPowerSetting};
+use crate::error::Error;
+use crate::nl80211traits::ToNlAttributes;
+use crate::wiphy::Wiphy;
+use neli::nlattr::Nlattr;
+
+/// Transmit power setting of a wiphy or an interface, levels are in signed mBm (100 * dBm)
+///
+/// The setting is write only: nl80211 reports the level in use, read from
+/// [`Interface::power`](struct.Interface.html#structfield.power), but not whether it is automatic,
+/// limited or fixed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TxPower {
+    /// Let the driver pick the power
+    Automatic,
+    /// Don't transmit above a level
+    Limited(i32),
+    /// Transmit at a fixed level
+    Fixed(i32),
+}
+
+impl TxPower {
+    /// Setting sent to nl80211
+    pub fn setting(&self) -> Nl80211TxPowerSetting {
+        match self {
+            TxPower::Automatic => Nl80211TxPowerSetting::TxPowerAutomatic,
+            TxPower::Limited(_) => Nl80211TxPowerSetting::TxPowerLimited,
+            TxPower::Fixed(_) => Nl80211TxPowerSetting::TxPowerFixed,
+        }
+    }
+
+    /// Power level of limited and fixed settings
+    pub fn level(&self) -> Option<i32> {
+        match self {
+            TxPower::Automatic => None,
+            TxPower::Limited(level) | TxPower::Fixed(level) => Some(*level),
+        }
+    }
+
+    /// Check the power level against the maximum power a wiphy allows on a channel, or on any of
+    /// its enabled channels when no frequency is given
+    ///
+    /// The check passes when the wiphy doesn't report the maximum power of the channels.
+    pub fn check(&self, wiphy: &Wiphy, frequency: Option<u32>) -> Result<(), Error> {
+        let level = match self.level() {
+            Some(level) => level,
+            None => return Ok(()),
+        };
+
+        let max_power = wiphy
+            .bands
+            .iter()
+            .flat_map(|band| band.channels.iter())
+            .filter(|channel| !channel.disabled)
+            .filter(|channel| frequency.is_none() || channel.frequency == frequency)
+            .filter_map(|channel| channel.max_tx_power)
+            .max();
+
+        match max_power {
+            Some(max_power) if i64::from(level) > i64::from(max_power) => {
+                Err(Error::Invalid(format!(
+                    "TX power of {} mBm exceeds the maximum of {} mBm",
+                    level, max_power
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ToNlAttributes for TxPower {
+    /// Build the TX power attributes of the nl80211 command CmdSetWiphy
+    fn to_attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Vec<u8>>>, Error> {
+        let mut attrs = vec![Nlattr::new(
+            None,
+            Nl80211Attr::AttrWiphyTxPowerSetting,
+            u32::from(u16::from(self.setting())).to_le_bytes().to_vec(),
+        )?];
+
+        if let Some(level) = self.level() {
+            attrs.push(Nlattr::new(
+                None,
+                Nl80211Attr::AttrWiphyTxPowerLevel,
+                level.to_le_bytes().to_vec(),
+            )?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test_txpower {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use crate::attr::Nl80211Bandc;
+    use crate::wiphy::{Band, Channel};
+
+    #[test]
+    fn test_to_attrs() {
+        let expected_attrs = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphyTxPowerSetting,
+                payload: vec![1, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphyTxPowerLevel,
+                payload: vec![156, 255, 255, 255],
+            },
+        ];
+
+        assert_eq!(TxPower::Limited(-100).to_attrs().unwrap(), expected_attrs);
+        assert_eq!(TxPower::Automatic.to_attrs().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_check() {
+        let channel = |frequency, max_tx_power| Channel {
+            frequency: Some(frequency),
+            max_tx_power: Some(max_tx_power),
+            ..Default::default()
+        };
+        let wiphy = Wiphy {
+            bands: vec![Band {
+                band: Nl80211Bandc::Band5ghz,
+                channels: vec![channel(5180, 2300), channel(5500, 3000)],
+                bitrates: vec![],
+                ht_capabilities: None,
+                ht_mcs_set: None,
+                vht_capabilities: None,
+                vht_mcs_set: None,
+            }],
+            ..Default::default()
+        };
+
+        assert!(TxPower::Fixed(2000).check(&wiphy, Some(5180)).is_ok());
+        assert!(TxPower::Fixed(2500).check(&wiphy, Some(5180)).is_err());
+        assert!(TxPower::Fixed(2500).check(&wiphy, None).is_ok());
+        assert!(TxPower::Fixed(2500).check(&Wiphy::default(), None).is_ok());
+        assert!(TxPower::Automatic.check(&wiphy, Some(5180)).is_ok());
+    }
+}
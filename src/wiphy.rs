@@ -0,0 +1,484 @@
+use crate::attr::*;
+use crate::channel::frequency_to_channel;
+use crate::cmd::Nl80211Cmd;
+use crate::error::Error;
+use crate::helpers::parse_string;
+use crate::ie::CipherSuite;
+use crate::nl80211traits::FromNlAttributeHandle;
+use byteorder::{LittleEndian, ReadBytesExt};
+use neli::nlattr::{AttrHandle, Nlattr};
+use std::fmt;
+
+/// A struct representing a wireless hardware device (wiphy) and its capabilities
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Wiphy {
+    /// index of wiphy, cf. /sys/class/ieee80211/<phyname>/index
+    pub index: Option<u32>,
+    /// Wiphy name (phy0, phy1...)
+    pub name: Option<String>,
+    /// Supported frequency bands
+    pub bands: Vec<Band>,
+    /// Supported interface types
+    pub iftypes: Vec<Nl80211Iftype>,
+    /// Supported cipher suites
+    pub cipher_suites: Vec<CipherSuite>,
+    /// Supported nl80211 commands
+    pub commands: Vec<Nl80211Cmd>,
+    /// Valid combinations of concurrent interfaces
+    pub interface_combinations: Vec<InterfaceCombination>,
+    /// Maximum number of SSIDs in a scan request
+    pub max_scan_ssids: Option<u8>,
+    /// Supported features bit field (Nl80211FeatureFlags)
+    pub feature_flags: Option<u32>,
+    /// Supported extended features bitmap, indexed by Nl80211ExtFeatureIndex
+    pub ext_features: Vec<u8>,
+}
+
+/// A frequency band supported by a wiphy
+#[derive(Clone, Debug, PartialEq)]
+pub struct Band {
+    /// Band identifier
+    pub band: Nl80211Bandc,
+    /// Channels of the band
+    pub channels: Vec<Channel>,
+    /// Legacy bitrates in units of 100 kb/s
+    pub bitrates: Vec<u32>,
+    /// HT capability information bit field
+    pub ht_capabilities: Option<u16>,
+    /// HT supported MCS set
+    pub ht_mcs_set: Option<Vec<u8>>,
+    /// VHT capability information bit field
+    pub vht_capabilities: Option<u32>,
+    /// VHT supported MCS set
+    pub vht_mcs_set: Option<Vec<u8>>,
+}
+
+/// A channel of a frequency band
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Channel {
+    /// Center frequency in MHz
+    pub frequency: Option<u32>,
+    /// The channel can't be used in the current regulatory domain
+    pub disabled: bool,
+    /// Initiating radiation (probing, beaconing...) is not allowed on this channel
+    pub no_ir: bool,
+    /// Radar detection is required on this channel
+    pub radar: bool,
+    /// Maximum transmission power in mBm (100 * dBm)
+    pub max_tx_power: Option<u32>,
+}
+
+impl Channel {
+    /// Channel number
+    pub fn number(&self) -> Option<u32> {
+        self.frequency.and_then(frequency_to_channel)
+    }
+}
+
+/// A valid combination of concurrent interfaces
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterfaceCombination {
+    /// Maximum number of interfaces of each group of types
+    pub limits: Vec<InterfaceLimit>,
+    /// Maximum total number of interfaces
+    pub max_interfaces: Option<u32>,
+    /// Number of different channels usable at the same time
+    pub num_channels: Option<u32>,
+    /// Beacon intervals of the interfaces must match
+    pub beacon_interval_match: bool,
+}
+
+/// Maximum number of interfaces of a group of types in an interface combination
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterfaceLimit {
+    pub max: Option<u32>,
+    pub iftypes: Vec<Nl80211Iftype>,
+}
+
+impl Wiphy {
+    /// Whether a feature is supported
+    pub fn has_feature(&self, feature: &Nl80211FeatureFlags) -> bool {
+        matches!(self.feature_flags, Some(flags) if flags & u32::from(feature) != 0)
+    }
+
+    /// Whether an extended feature is supported
+    pub fn has_ext_feature(&self, feature: &Nl80211ExtFeatureIndex) -> bool {
+        let index = usize::from(u16::from(feature));
+        matches!(self.ext_features.get(index / 8), Some(byte) if byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Update the wiphy with one of the messages of a split dump
+    ///
+    /// The kernel splits the description of a wiphy into several messages, channels of a band may
+    /// be spread over consecutive messages.
+    pub(crate) fn update(&mut self, handle: AttrHandle<Nl80211Attr>) -> Result<(), Error> {
+        for attr in handle.iter() {
+            let mut payload = &attr.payload[..];
+            match attr.nla_type {
+                Nl80211Attr::AttrWiphy => self.index = Some(payload.read_u32::<LittleEndian>()?),
+                Nl80211Attr::AttrWiphyName => self.name = Some(parse_string(&attr.payload)),
+                Nl80211Attr::AttrWiphyBands => {
+                    for band_attr in attr.get_nested_attributes::<Nl80211Bandc>()?.iter() {
+                        let band = match self
+                            .bands
+                            .iter_mut()
+                            .find(|band| band.band == band_attr.nla_type)
+                        {
+                            Some(band) => band,
+                            None => {
+                                self.bands.push(Band::new(band_attr.nla_type.clone()));
+                                self.bands.last_mut().unwrap()
+                            }
+                        };
+                        band.update(band_attr)?;
+                    }
+                }
+                Nl80211Attr::AttrSupportedIftypes => {
+                    self.iftypes = attr
+                        .get_nested_attributes::<Nl80211Iftype>()?
+                        .iter()
+                        .map(|iftype| iftype.nla_type.clone())
+                        .collect();
+                }
+                Nl80211Attr::AttrCipherSuites => {
+                    self.cipher_suites = attr
+                        .payload
+                        .chunks_exact(4)
+                        .map(|mut suite| suite.read_u32::<LittleEndian>().map(CipherSuite::from))
+                        .collect::<Result<_, _>>()?;
+                }
+                Nl80211Attr::AttrSupportedCommands => {
+                    for cmd in attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        let cmd = (&cmd.payload[..]).read_u32::<LittleEndian>()?;
+                        self.commands.push(Nl80211Cmd::from(cmd as u8));
+                    }
+                }
+                Nl80211Attr::AttrInterfaceCombinations => {
+                    for combination in attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        self.interface_combinations
+                            .push(InterfaceCombination::from_attr(combination)?);
+                    }
+                }
+                Nl80211Attr::AttrMaxNumScanSsids => self.max_scan_ssids = Some(payload.read_u8()?),
+                Nl80211Attr::AttrFeatureFlags => {
+                    self.feature_flags = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211Attr::AttrExtFeatures => self.ext_features = attr.payload.clone(),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromNlAttributeHandle for Wiphy {
+    /// Parse netlink messages returned by the nl80211 command CmdGetWiphy
+    fn from_handle(handle: AttrHandle<Nl80211Attr>) -> Result<Wiphy, Error> {
+        let mut wiphy = Wiphy {
+            ..Default::default()
+        };
+        wiphy.update(handle)?;
+        Ok(wiphy)
+    }
+}
+
+impl Band {
+    fn new(band: Nl80211Bandc) -> Band {
+        Band {
+            band,
+            channels: Vec::new(),
+            bitrates: Vec::new(),
+            ht_capabilities: None,
+            ht_mcs_set: None,
+            vht_capabilities: None,
+            vht_mcs_set: None,
+        }
+    }
+
+    /// Update the band with a nested attribute of AttrWiphyBands
+    fn update(&mut self, attr: &Nlattr<Nl80211Bandc, Vec<u8>>) -> Result<(), Error> {
+        for band_attr in attr.get_nested_attributes::<Nl80211BandAttr>()?.iter() {
+            let mut payload = &band_attr.payload[..];
+            match band_attr.nla_type {
+                Nl80211BandAttr::BandAttrFreqs => {
+                    for frequency in band_attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        self.channels.push(Channel::from_attr(frequency)?);
+                    }
+                }
+                Nl80211BandAttr::BandAttrRates => {
+                    for rate in band_attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        let rate_handle = rate.get_nested_attributes::<Nl80211BitrateAttr>()?;
+                        if let Some(rate) =
+                            rate_handle.get_attribute(Nl80211BitrateAttr::BitrateAttrRate)
+                        {
+                            self.bitrates
+                                .push((&rate.payload[..]).read_u32::<LittleEndian>()?);
+                        }
+                    }
+                }
+                Nl80211BandAttr::BandAttrHtCapa => {
+                    self.ht_capabilities = Some(payload.read_u16::<LittleEndian>()?)
+                }
+                Nl80211BandAttr::BandAttrHtMcsSet => {
+                    self.ht_mcs_set = Some(band_attr.payload.clone())
+                }
+                Nl80211BandAttr::BandAttrVhtCapa => {
+                    self.vht_capabilities = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211BandAttr::BandAttrVhtMcsSet => {
+                    self.vht_mcs_set = Some(band_attr.payload.clone())
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Channel {
+    /// Parse a nested attribute of BandAttrFreqs
+    fn from_attr(attr: &Nlattr<NlaNested, Vec<u8>>) -> Result<Channel, Error> {
+        let mut channel = Channel {
+            ..Default::default()
+        };
+        for sub_attr in attr.get_nested_attributes::<Nl80211FrequencyAttr>()?.iter() {
+            let mut payload = &sub_attr.payload[..];
+            match sub_attr.nla_type {
+                Nl80211FrequencyAttr::FrequencyAttrFreq => {
+                    channel.frequency = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211FrequencyAttr::FrequencyAttrDisabled => channel.disabled = true,
+                Nl80211FrequencyAttr::FrequencyAttrNoIr => channel.no_ir = true,
+                Nl80211FrequencyAttr::FrequencyAttrRadar => channel.radar = true,
+                Nl80211FrequencyAttr::FrequencyAttrMaxTxPower => {
+                    channel.max_tx_power = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                _ => (),
+            }
+        }
+        Ok(channel)
+    }
+}
+
+impl InterfaceCombination {
+    /// Parse a nested attribute of AttrInterfaceCombinations
+    fn from_attr(attr: &Nlattr<NlaNested, Vec<u8>>) -> Result<InterfaceCombination, Error> {
+        let mut combination = InterfaceCombination {
+            ..Default::default()
+        };
+        for sub_attr in attr
+            .get_nested_attributes::<Nl80211IfCombinationAttrs>()?
+            .iter()
+        {
+            let mut payload = &sub_attr.payload[..];
+            match sub_attr.nla_type {
+                Nl80211IfCombinationAttrs::IfaceCombLimits => {
+                    for limit in sub_attr.get_nested_attributes::<NlaNested>()?.iter() {
+                        let mut interface_limit = InterfaceLimit {
+                            ..Default::default()
+                        };
+                        for limit_attr in limit
+                            .get_nested_attributes::<Nl80211IfaceLimitAttrs>()?
+                            .iter()
+                        {
+                            match limit_attr.nla_type {
+                                Nl80211IfaceLimitAttrs::IfaceLimitMax => {
+                                    interface_limit.max =
+                                        Some((&limit_attr.payload[..]).read_u32::<LittleEndian>()?)
+                                }
+                                Nl80211IfaceLimitAttrs::IfaceLimitTypes => {
+                                    interface_limit.iftypes = limit_attr
+                                        .get_nested_attributes::<Nl80211Iftype>()?
+                                        .iter()
+                                        .map(|iftype| iftype.nla_type.clone())
+                                        .collect()
+                                }
+                                _ => (),
+                            }
+                        }
+                        combination.limits.push(interface_limit);
+                    }
+                }
+                Nl80211IfCombinationAttrs::IfaceCombMaxnum => {
+                    combination.max_interfaces = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211IfCombinationAttrs::IfaceCombNumChannels => {
+                    combination.num_channels = Some(payload.read_u32::<LittleEndian>()?)
+                }
+                Nl80211IfCombinationAttrs::IfaceCombStaApBiMatch => {
+                    combination.beacon_interval_match = true
+                }
+                _ => (),
+            }
+        }
+        Ok(combination)
+    }
+}
+
+impl fmt::Display for Wiphy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Vec::new();
+
+        if let Some(name) = &self.name {
+            result.push(format!("wiphy : {}", name))
+        };
+
+        if let Some(index) = &self.index {
+            result.push(format!("index : {}", index))
+        };
+
+        for band in &self.bands {
+            let channels: Vec<String> = band
+                .channels
+                .iter()
+                .filter(|channel| !channel.disabled)
+                .filter_map(|channel| channel.number())
+                .map(|number| number.to_string())
+                .collect();
+            result.push(format!("{:?} channels : {}", band.band, channels.join(" ")))
+        }
+
+        if !self.iftypes.is_empty() {
+            let iftypes: Vec<String> = self.iftypes.iter().map(|i| format!("{:?}", i)).collect();
+            result.push(format!("interface types : {}", iftypes.join(", ")))
+        };
+
+        if !self.cipher_suites.is_empty() {
+            let ciphers: Vec<String> = self
+                .cipher_suites
+                .iter()
+                .map(|c| format!("{:?}", c))
+                .collect();
+            result.push(format!("cipher suites : {}", ciphers.join(", ")))
+        };
+
+        if let Some(max_scan_ssids) = &self.max_scan_ssids {
+            result.push(format!("max scan ssids : {}", max_scan_ssids))
+        };
+
+        write!(f, "{}", result.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test_wiphy {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+
+    #[test]
+    fn test_parse_split_dump() {
+        let first_message = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphy,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 9,
+                nla_type: AttrWiphyName,
+                payload: vec![112, 104, 121, 48, 0],
+            },
+            Nlattr {
+                nla_len: 5,
+                nla_type: AttrMaxNumScanSsids,
+                payload: vec![20],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrCipherSuites,
+                payload: vec![1, 172, 15, 0, 4, 172, 15, 0],
+            },
+            Nlattr {
+                nla_len: 12,
+                nla_type: AttrSupportedIftypes,
+                payload: vec![4, 0, 2, 0, 4, 0, 3, 0],
+            },
+        ];
+        let second_message = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphy,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 40,
+                nla_type: AttrWiphyBands,
+                payload: vec![
+                    36, 0, 0, 0, 32, 0, 1, 0, 28, 0, 0, 0, 8, 0, 1, 0, 108, 9, 0, 0, 8, 0, 6, 0,
+                    208, 7, 0, 0, 4, 0, 3, 0, 4, 0, 5, 0,
+                ],
+            },
+        ];
+        let third_message = vec![
+            Nlattr {
+                nla_len: 8,
+                nla_type: AttrWiphy,
+                payload: vec![0, 0, 0, 0],
+            },
+            Nlattr {
+                nla_len: 32,
+                nla_type: AttrWiphyBands,
+                payload: vec![
+                    28, 0, 0, 0, 24, 0, 1, 0, 20, 0, 1, 0, 8, 0, 1, 0, 113, 9, 0, 0, 4, 0, 2, 0, 4,
+                    0, 3, 0,
+                ],
+            },
+        ];
+
+        let mut wiphy = Wiphy::from_handle(AttrHandle::Owned(first_message)).unwrap();
+        wiphy.update(AttrHandle::Owned(second_message)).unwrap();
+        wiphy.update(AttrHandle::Owned(third_message)).unwrap();
+
+        let expected_wiphy = Wiphy {
+            index: Some(0),
+            name: Some("phy0".into()),
+            bands: vec![Band {
+                band: Nl80211Bandc::Band2ghz,
+                channels: vec![
+                    Channel {
+                        frequency: Some(2412),
+                        disabled: false,
+                        no_ir: true,
+                        radar: true,
+                        max_tx_power: Some(2000),
+                    },
+                    Channel {
+                        frequency: Some(2417),
+                        disabled: true,
+                        no_ir: true,
+                        radar: false,
+                        max_tx_power: None,
+                    },
+                ],
+                bitrates: vec![],
+                ht_capabilities: None,
+                ht_mcs_set: None,
+                vht_capabilities: None,
+                vht_mcs_set: None,
+            }],
+            iftypes: vec![Nl80211Iftype::IftypeStation, Nl80211Iftype::IftypeAp],
+            cipher_suites: vec![CipherSuite::Wep40, CipherSuite::Ccmp],
+            max_scan_ssids: Some(20),
+            ..Default::default()
+        };
+
+        assert_eq!(wiphy, expected_wiphy)
+    }
+
+    #[test]
+    fn test_ext_features() {
+        let wiphy = Wiphy {
+            feature_flags: Some(1 << 5 | 1 << 29),
+            ext_features: vec![0b0000_0010],
+            ..Default::default()
+        };
+
+        assert!(wiphy.has_feature(&Nl80211FeatureFlags::FeatureSae));
+        assert!(!wiphy.has_feature(&Nl80211FeatureFlags::FeatureApScan));
+        assert!(wiphy.has_feature(&Nl80211FeatureFlags::FeatureScanRandomMacAddr));
+        assert!(!wiphy.has_feature(&Nl80211FeatureFlags::FeatureMacOnCreate));
+        assert!(wiphy.has_ext_feature(&Nl80211ExtFeatureIndex::ExtFeatureRrm));
+        assert!(!wiphy.has_ext_feature(&Nl80211ExtFeatureIndex::ExtFeatureVhtIbss));
+        assert!(!wiphy.has_ext_feature(&Nl80211ExtFeatureIndex::ExtFeatureBeaconRateVht));
+    }
+}